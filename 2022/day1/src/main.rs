@@ -0,0 +1,142 @@
+use std::env;
+use std::fs;
+
+fn parse_calories(input: &str) -> Vec<usize> {
+    let mut cals = 0;
+    let mut cals_list: Vec<usize> = Vec::new();
+    for line in input.lines() {
+        if line.trim().len() > 0 {
+            let count = line.parse::<usize>().unwrap();
+            cals += count;
+        } else {
+            cals_list.push(cals);
+            cals = 0;
+        }
+    }
+    if cals > 0 {
+        cals_list.push(cals);
+    }
+    cals_list
+}
+
+// Calories carried by the n elves carrying the most
+fn top_calories(calories: &[usize], n: usize) -> usize {
+    let mut cals_list = calories.to_vec();
+    cals_list.sort_unstable_by(|a, b| b.cmp(a));
+    cals_list.iter().take(n).sum()
+}
+
+fn solve_part1(input: &str) -> usize {
+    top_calories(&parse_calories(input), 1)
+}
+
+fn solve_part2(input: &str) -> usize {
+    top_calories(&parse_calories(input), 3)
+}
+
+struct Repl {
+    calories: Vec<usize>,
+}
+
+impl Repl {
+    fn new(input: &str) -> Repl {
+        Repl {
+            calories: parse_calories(input),
+        }
+    }
+}
+
+impl aoc_repl::Introspect for Repl {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![(
+            "top",
+            "<n>",
+            "Sum the calories carried by the n elves carrying the most",
+        )]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "top" => {
+                let n: usize = aoc_repl::arg(args, 0, "n")?;
+                Ok(top_calories(&self.calories, n).to_string())
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(top_calories(&self.calories, 1).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(top_calories(&self.calories, 3).to_string())
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
+    let filename = args
+        .get(1)
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
+
+    println!("Reading input from {}", filename);
+    let input = fs::read_to_string(filename).expect("Failed to read file");
+
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
+    println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
+
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
+    println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
+}
+
+#[cfg(test)]
+mod tests_day1 {
+    use super::*;
+
+    const EXAMPLE1: &str = "
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn test1_1() {
+        assert_eq!(solve_part1(EXAMPLE1), 24000);
+    }
+
+    #[test]
+    fn test2_1() {
+        assert_eq!(solve_part2(EXAMPLE1), 45000);
+    }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert_eq!(repl.execute("top", &["1"]), Ok("24000".to_string()));
+        assert_eq!(repl.execute("top", &["3"]), Ok("45000".to_string()));
+        assert!(repl.execute("top", &["x"]).is_err());
+        assert_eq!(repl.part1(), Ok("24000".to_string()));
+        assert_eq!(repl.part2(), Ok("45000".to_string()));
+    }
+}
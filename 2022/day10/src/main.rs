@@ -37,9 +37,7 @@ fn sample_signal_strength(cycle: isize, x: isize) -> Option<isize> {
     }
 }
 
-fn solve_part1(input: &str) -> isize {
-    let instructions = read_instructions(input);
-
+fn signal_strength_sum(instructions: &[Instr]) -> isize {
     let mut x: isize = 1;
     let mut cycle: isize = 0;
     let mut sum: isize = 0;
@@ -86,9 +84,7 @@ fn print_screen(label: &str, s: &str) {
     }
 }
 
-fn solve_part2(input: &str) -> String {
-    let instructions = read_instructions(input);
-
+fn draw_pixels(instructions: &[Instr]) -> String {
     let mut sprite_position: isize = 1;
     let mut sprite_positions: Vec<isize> = Vec::new();
     for instr in instructions.iter() {
@@ -118,21 +114,76 @@ fn solve_part2(input: &str) -> String {
         .collect::<String>()
 }
 
+fn solve_part1(input: &str) -> isize {
+    signal_strength_sum(&read_instructions(input))
+}
+
+fn solve_part2(input: &str) -> String {
+    draw_pixels(&read_instructions(input))
+}
+
+struct Repl {
+    instructions: Vec<Instr>,
+}
+
+impl Repl {
+    fn new(input: &str) -> Repl {
+        Repl {
+            instructions: read_instructions(input),
+        }
+    }
+}
+
+impl aoc_repl::Introspect for Repl {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![("screen", "", "Draw the screen of part 2")]
+    }
+
+    fn execute(&mut self, command: &str, _args: &[&str]) -> Result<String, String> {
+        match command {
+            "screen" => {
+                let pixels = draw_pixels(&self.instructions);
+                let rows: Vec<&str> = (0..pixels.len() / 40)
+                    .map(|row| &pixels[row * 40..(row + 1) * 40])
+                    .collect();
+                Ok(rows.join("\n"))
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(signal_strength_sum(&self.instructions).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(draw_pixels(&self.instructions))
+    }
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
     const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
     let filename = args
         .get(1)
-        .expect(format!("Usage: {} input-filename", PACKAGE_NAME.unwrap()).as_str());
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
 
     println!("Reading input from {}", filename);
     let input = fs::read_to_string(filename).expect("Failed to read file");
 
-    let answer1 = solve_part1(&input);
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
     println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
 
-    let answer2 = solve_part2(&input);
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
     print_screen("Answer 2", &answer2);
+    println!("Stats 2: {}", stats2);
 }
 
 #[cfg(test)]
@@ -324,4 +375,15 @@ noop";
 #####.....#####.....#####.....#####.....
 ######......######......######......####
 #######.......#######.......#######.....";
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        let screen = repl.execute("screen", &[]).unwrap();
+        assert_eq!(screen.lines().count(), 6);
+        assert!(screen.starts_with("##..##..##..##..##..##..##..##..##..##.."));
+        assert_eq!(repl.part1(), Ok("13140".to_string()));
+        assert_eq!(repl.part2(), Ok(screen.replace('\n', "")));
+    }
 }
@@ -46,7 +46,7 @@ fn starting_items_from_str(s: &str) -> Result<Vec<usize>, String> {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 enum Operation {
     Add(usize), // new = old + x
     Mul(usize), // new = old * x
@@ -157,7 +157,7 @@ impl Action {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 struct Monkey {
     id: usize,
     items: Vec<usize>,
@@ -237,47 +237,80 @@ fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, String> {
     Ok(monkeys)
 }
 
+fn run_round<F: Fn(usize) -> usize>(monkeys: &mut [Monkey], reduce_worry: F) {
+    for monkey_id in 0..monkeys.len() {
+        let throws: Vec<(usize, usize)> = {
+            let monkey = &monkeys[monkey_id];
+            monkey
+                .items
+                .iter()
+                .map(|worry| {
+                    let new_worry = reduce_worry(monkey.op.inspect(*worry));
+                    let next_monkey = if new_worry % monkey.test_divisor == 0 {
+                        monkey.throw_to_true
+                    } else {
+                        monkey.throw_to_false
+                    };
+                    (next_monkey, new_worry)
+                })
+                .collect()
+        };
+
+        {
+            let monkey = &mut monkeys[monkey_id];
+            monkey.inspect_count += monkey.items.len();
+            monkey.items.clear();
+        }
+        for (next_monkey, worry) in throws {
+            monkeys[next_monkey].items.push(worry);
+        }
+    }
+}
+
+// Product of the two largest inspect counts
+fn monkey_business(monkeys: &[Monkey]) -> usize {
+    let mut inspect_counts: Vec<usize> = monkeys.iter().map(|m| m.inspect_count).collect();
+    inspect_counts.sort();
+    assert!(inspect_counts.len() >= 2);
+    inspect_counts.iter().rev().take(2).product()
+}
+
+fn run_relieved_rounds(monkeys: &mut [Monkey], round_count: usize) {
+    for _round in 0..round_count {
+        run_round(monkeys, |worry| worry / 3);
+    }
+}
+
+// Worry is kept small by the product of all divisors, which keeps every test intact
+fn run_small_worry_rounds(monkeys: &mut [Monkey], round_count: usize) {
+    let max_combined_divisor: usize = monkeys.iter().fold(1, |acc, m| acc * m.test_divisor);
+
+    for _round in 0..round_count {
+        run_round(monkeys, |worry| worry % max_combined_divisor);
+    }
+}
+
+fn part1_business(monkeys: &[Monkey]) -> usize {
+    let mut monkeys = monkeys.to_vec();
+    run_relieved_rounds(&mut monkeys, 20);
+    monkey_business(&monkeys)
+}
+
+fn part2_business(monkeys: &[Monkey]) -> usize {
+    let mut monkeys = monkeys.to_vec();
+    run_small_worry_rounds(&mut monkeys, 10000);
+    monkey_business(&monkeys)
+}
+
 fn solve_part1(input: &str) -> usize {
-    let mut monkeys = match parse_monkeys(input) {
+    let monkeys = match parse_monkeys(input) {
         Ok(monkeys) => monkeys,
         Err(e) => panic!("Error: {}", e),
     };
 
     monkeys.iter().for_each(|m| println!("{:?}", m));
 
-    for _round in 0..20 {
-        for monkey_id in 0..monkeys.len() {
-            let throws: Vec<(usize, usize)> = {
-                let monkey = &monkeys[monkey_id];
-                monkey
-                    .items
-                    .iter()
-                    .map(|worry| {
-                        let new_worry = monkey.op.inspect(*worry) / 3;
-                        let next_monkey = if new_worry % monkey.test_divisor == 0 {
-                            monkey.throw_to_true
-                        } else {
-                            monkey.throw_to_false
-                        };
-                        (next_monkey, new_worry)
-                    })
-                    .collect()
-            };
-
-            {
-                let monkey = &mut monkeys[monkey_id];
-                monkey.inspect_count += monkey.items.len();
-                monkey.items.clear();
-            }
-            for (next_monkey, worry) in throws {
-                monkeys[next_monkey].items.push(worry);
-            }
-        }
-    }
-
-    monkeys.sort_by_key(|m| m.inspect_count);
-    assert!(monkeys.len() >= 2);
-    monkeys.pop().unwrap().inspect_count * monkeys.pop().unwrap().inspect_count
+    part1_business(&monkeys)
 }
 
 fn run_monkey_rounds(input: &str, round_count: usize) -> Vec<usize> {
@@ -286,48 +319,113 @@ fn run_monkey_rounds(input: &str, round_count: usize) -> Vec<usize> {
         Err(e) => panic!("Error: {}", e),
     };
 
-    let max_combined_divisor: usize = monkeys.iter().fold(1, |acc, m| acc * m.test_divisor);
+    run_small_worry_rounds(&mut monkeys, round_count);
 
-    for _round in 0..round_count {
-        for monkey_id in 0..monkeys.len() {
-            let throws: Vec<(usize, usize)> = {
-                let monkey = &monkeys[monkey_id];
-                monkey
-                    .items
-                    .iter()
-                    .map(|worry| {
-                        let new_worry = monkey.op.inspect(*worry) % max_combined_divisor;
-                        let next_monkey = if new_worry % monkey.test_divisor == 0 {
-                            monkey.throw_to_true
-                        } else {
-                            monkey.throw_to_false
-                        };
-                        (next_monkey, new_worry)
-                    })
-                    .collect()
-            };
+    monkeys.iter().map(|m| m.inspect_count).collect()
+}
+
+fn solve_part2(input: &str) -> usize {
+    let monkeys = match parse_monkeys(input) {
+        Ok(monkeys) => monkeys,
+        Err(e) => panic!("Error: {}", e),
+    };
+    part2_business(&monkeys)
+}
+
+struct Repl {
+    monkeys: Vec<Monkey>,
+    initial: Vec<Monkey>,
+    round: usize,
+}
+
+impl Repl {
+    fn new(input: &str) -> Repl {
+        let monkeys = match parse_monkeys(input) {
+            Ok(monkeys) => monkeys,
+            Err(e) => panic!("Error: {}", e),
+        };
+        Repl {
+            initial: monkeys.clone(),
+            monkeys,
+            round: 0,
+        }
+    }
+
+    fn items(&self) -> String {
+        self.monkeys
+            .iter()
+            .map(|m| format!("Monkey {}: {:?}", m.id, m.items))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
 
-            {
-                let monkey = &mut monkeys[monkey_id];
-                monkey.inspect_count += monkey.items.len();
-                monkey.items.clear();
+impl aoc_repl::Introspect for Repl {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![
+            ("print", "", "Print the items held by each monkey"),
+            ("monkey", "<id>", "Print everything about a monkey"),
+            (
+                "round",
+                "[n]",
+                "Play n rounds where worry is divided by 3, as in part 1",
+            ),
+            (
+                "round2",
+                "[n]",
+                "Play n rounds where worry is kept small, as in part 2",
+            ),
+            (
+                "counts",
+                "",
+                "Print how many items each monkey has inspected",
+            ),
+            ("business", "", "Print the level of monkey business"),
+            ("reset", "", "Go back to the parsed monkeys"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "print" => Ok(format!("After round {}\n{}", self.round, self.items())),
+            "monkey" => {
+                let id: usize = aoc_repl::arg(args, 0, "id")?;
+                let monkey = self.monkeys.get(id).ok_or(format!("No monkey {}", id))?;
+                Ok(format!("{:?}", monkey))
             }
-            for (next_monkey, worry) in throws {
-                monkeys[next_monkey].items.push(worry);
+            "round" | "round2" => {
+                let count: usize = aoc_repl::arg_or(args, 0, "n", 1)?;
+                if command == "round" {
+                    run_relieved_rounds(&mut self.monkeys, count);
+                } else {
+                    run_small_worry_rounds(&mut self.monkeys, count);
+                }
+                self.round += count;
+                Ok(format!("After round {}\n{}", self.round, self.items()))
+            }
+            "counts" => Ok(self
+                .monkeys
+                .iter()
+                .map(|m| format!("Monkey {} inspected items {} times", m.id, m.inspect_count))
+                .collect::<Vec<String>>()
+                .join("\n")),
+            "business" => Ok(monkey_business(&self.monkeys).to_string()),
+            "reset" => {
+                self.monkeys = self.initial.clone();
+                self.round = 0;
+                Ok(self.items())
             }
+            _ => Err(format!("Unhandled command {}", command)),
         }
     }
 
-    monkeys.iter().map(|m| m.inspect_count).collect()
-}
+    fn part1(&self) -> Result<String, String> {
+        Ok(part1_business(&self.initial).to_string())
+    }
 
-fn solve_part2(input: &str) -> usize {
-    let mut inspect_counts = run_monkey_rounds(input, 10000);
-    inspect_counts.sort();
-    assert!(inspect_counts.len() >= 2);
-    let inspect_count1 = inspect_counts.pop().unwrap();
-    let inspect_count2 = inspect_counts.pop().unwrap();
-    inspect_count1 * inspect_count2
+    fn part2(&self) -> Result<String, String> {
+        Ok(part2_business(&self.initial).to_string())
+    }
 }
 
 fn main() {
@@ -335,16 +433,24 @@ fn main() {
     const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
     let filename = args
         .get(1)
-        .expect(format!("Usage: {} input-filename", PACKAGE_NAME.unwrap()).as_str());
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
 
     println!("Reading input from {}", filename);
     let input = fs::read_to_string(filename).expect("Failed to read file");
 
-    let answer1 = solve_part1(&input);
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
     println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
 
-    let answer2 = solve_part2(&input);
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
     println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
 }
 
 #[cfg(test)]
@@ -476,4 +582,17 @@ Monkey 3:
     fn test2_rounds_20() {
         assert_eq!(run_monkey_rounds(EXAMPLE1, 20), vec![99, 97, 8, 103]);
     }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert!(repl.execute("round", &["20"]).is_ok());
+        assert_eq!(repl.execute("business", &[]), Ok("10605".to_string()));
+        assert!(repl.execute("reset", &[]).is_ok());
+        assert!(repl.execute("round2", &["10000"]).is_ok());
+        assert_eq!(repl.execute("business", &[]), Ok("2713310158".to_string()));
+        assert_eq!(repl.part1(), Ok("10605".to_string()));
+        assert_eq!(repl.part2(), Ok("2713310158".to_string()));
+    }
 }
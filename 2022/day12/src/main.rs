@@ -0,0 +1,993 @@
+#![allow(dead_code)]
+
+use aoc_graph::Path;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::hash::Hash;
+use std::str::FromStr;
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+struct Vector2 {
+    x: isize,
+    y: isize,
+}
+
+impl Vector2 {
+    fn new(x: isize, y: isize) -> Vector2 {
+        Vector2 { x, y }
+    }
+}
+
+impl fmt::Display for Vector2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+const STRAIGHT: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+// How one may move from a location to an adjacent one, and what that costs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Rules {
+    diagonals: bool,
+    max_climb: Option<u8>,
+    max_descent: Option<u8>,
+    step_cost: usize,
+    climb_cost: usize,
+    descent_cost: usize,
+}
+
+impl Rules {
+    // Four directions, climbing at most one and dropping any height, every step costs one
+    fn puzzle() -> Rules {
+        Rules {
+            diagonals: false,
+            max_climb: Some(1),
+            max_descent: None,
+            step_cost: 1,
+            climb_cost: 0,
+            descent_cost: 0,
+        }
+    }
+
+    fn directions(&self) -> &'static [(isize, isize)] {
+        if self.diagonals {
+            &[
+                (-1, 0),
+                (1, 0),
+                (0, -1),
+                (0, 1),
+                (-1, -1),
+                (1, -1),
+                (-1, 1),
+                (1, 1),
+            ]
+        } else {
+            &STRAIGHT
+        }
+    }
+
+    // Cost of a step from one height to another, None when the step is not allowed
+    fn cost(&self, from: u8, to: u8) -> Option<usize> {
+        if to > from {
+            let climb = to - from;
+            if self.max_climb.is_some_and(|max| climb > max) {
+                return None;
+            }
+            Some(self.step_cost + climb as usize * self.climb_cost)
+        } else {
+            let descent = from - to;
+            if self.max_descent.is_some_and(|max| descent > max) {
+                return None;
+            }
+            Some(self.step_cost + descent as usize * self.descent_cost)
+        }
+    }
+
+    // A lower bound of the cost between two locations, for A*
+    fn min_cost(&self, from: &Vector2, to: &Vector2) -> usize {
+        let steps = if self.diagonals {
+            aoc_graph::chebyshev((from.x, from.y), (to.x, to.y))
+        } else {
+            aoc_graph::manhattan((from.x, from.y), (to.x, to.y))
+        };
+        steps * self.step_cost
+    }
+
+    // True when cost and number of steps are the same thing
+    fn counts_steps(&self) -> bool {
+        self.step_cost == 1 && self.climb_cost == 0 && self.descent_cost == 0
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules::puzzle()
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limit = |l: Option<u8>| l.map_or("any".to_string(), |l| l.to_string());
+        write!(
+            f,
+            "diagonals: {}, climb: {}, descent: {}, step cost: {}, climb cost: {}, descent cost: {}",
+            if self.diagonals { "on" } else { "off" },
+            limit(self.max_climb),
+            limit(self.max_descent),
+            self.step_cost,
+            self.climb_cost,
+            self.descent_cost
+        )
+    }
+}
+
+struct Map {
+    row_major: Vec<u8>,
+    size: Vector2,
+    start: Vector2,
+    end: Vector2,
+    rules: Rules,
+}
+
+impl Map {
+    fn new(row_major: Vec<u8>, size: Vector2, start: Vector2, end: Vector2) -> Map {
+        Map {
+            row_major,
+            size,
+            start,
+            end,
+            rules: Rules::default(),
+        }
+    }
+
+    fn adjacent<'a>(&'a self, loc: &'a Vector2) -> impl Iterator<Item = Vector2> + 'a {
+        self.rules
+            .directions()
+            .iter()
+            .map(|(dx, dy)| Vector2::new(loc.x + dx, loc.y + dy))
+            .filter(|n| self.is_valid_loc(n))
+    }
+
+    // Locations that can be stepped to from loc, with the cost of the step
+    fn neighbours(&self, loc: &Vector2) -> Vec<(Vector2, usize)> {
+        let height = self.height(loc);
+        self.adjacent(loc)
+            .filter_map(|n| Some((n, self.rules.cost(height, self.height(&n))?)))
+            .collect()
+    }
+
+    fn is_valid_loc(&self, loc: &Vector2) -> bool {
+        return 0 <= loc.x && loc.x < self.size.x && 0 <= loc.y && loc.y < self.size.y;
+    }
+
+    fn height(&self, loc: &Vector2) -> u8 {
+        let index = (loc.y * self.size.x + loc.x) as usize;
+        self.row_major[index]
+    }
+
+    fn loc_at(&self, index: usize) -> Vector2 {
+        let width = self.size.x as usize;
+        Vector2::new((index % width) as isize, (index / width) as isize)
+    }
+}
+
+// Heights as letters with an optional marker, or a route drawn with arrows as in the puzzle
+struct DebugMap<'a> {
+    map: &'a Map,
+    loc: Option<&'a Vector2>,
+    route: &'a [Vector2],
+    region: &'a [Vector2],
+    elevation: bool,
+}
+
+impl<'a> DebugMap<'a> {
+    fn new(map: &'a Map, loc: &'a Vector2) -> DebugMap<'a> {
+        DebugMap {
+            map,
+            loc: Some(loc),
+            route: &[],
+            region: &[],
+            elevation: false,
+        }
+    }
+
+    fn with_route(map: &'a Map, route: &'a [Vector2]) -> DebugMap<'a> {
+        DebugMap {
+            map,
+            loc: None,
+            route,
+            region: &[],
+            elevation: false,
+        }
+    }
+
+    // Heights in the region are drawn in upper case
+    fn with_region(map: &'a Map, region: &'a [Vector2]) -> DebugMap<'a> {
+        DebugMap {
+            map,
+            loc: None,
+            route: &[],
+            region,
+            elevation: false,
+        }
+    }
+
+    // Keeps the height letters next to the route, on a background shaded by height
+    fn with_elevation(mut self) -> DebugMap<'a> {
+        self.elevation = true;
+        self
+    }
+
+    fn arrow(from: &Vector2, to: &Vector2) -> char {
+        match (to.x - from.x, to.y - from.y) {
+            (1, 0) => '>',
+            (-1, 0) => '<',
+            (0, -1) => '^',
+            (0, 1) => 'v',
+            (1, -1) | (-1, 1) => '/',
+            (1, 1) | (-1, -1) => '\\',
+            _ => '?',
+        }
+    }
+}
+
+impl<'a> fmt::Debug for DebugMap<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arrows: HashMap<Vector2, char> = self
+            .route
+            .windows(2)
+            .map(|w| (w[0], DebugMap::arrow(&w[0], &w[1])))
+            .collect();
+        let region: HashSet<&Vector2> = self.region.iter().collect();
+        let destination = self.route.last();
+        for y in 0..self.map.size.y {
+            for x in 0..self.map.size.x {
+                let loc = Vector2::new(x, y);
+                let height = self.map.height(&loc);
+                let c = if self.loc == Some(&loc) {
+                    '*'
+                } else if let Some(arrow) = arrows.get(&loc) {
+                    *arrow
+                } else if destination == Some(&loc) {
+                    if loc == self.map.end {
+                        'E'
+                    } else {
+                        '*'
+                    }
+                } else if destination.is_some() && !self.elevation {
+                    '.'
+                } else if region.contains(&loc) {
+                    (height + b'A') as char
+                } else {
+                    (height + b'a') as char
+                };
+                if self.elevation {
+                    // 24 steps of grey from black to white, with text in a contrasting grey
+                    let background = 232 + height as usize * 23 / 25;
+                    let foreground = if height < 13 { 255 } else { 232 };
+                    write!(f, "\x1b[48;5;{}m\x1b[38;5;{}m{}", background, foreground, c)?;
+                } else {
+                    write!(f, "{}", c)?;
+                }
+            }
+            if self.elevation {
+                write!(f, "\x1b[0m")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Map {
+    type Err = ();
+    fn from_str(input: &str) -> Result<Map, ()> {
+        let mut width = 0;
+        let mut start: Option<Vector2> = None;
+        let mut end: Option<Vector2> = None;
+        let row_major: Vec<u8> = input
+            .lines()
+            .filter_map(|l| if l.len() > 0 { Some(l) } else { None })
+            .enumerate()
+            .map(|(y, l)| {
+                let row: Vec<u8> = l
+                    .chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        let height_char = match c {
+                            'a'..='z' => c,
+                            'S' => {
+                                start = Some(Vector2::new(x as isize, y as isize));
+                                'a'
+                            }
+                            'E' => {
+                                end = Some(Vector2::new(x as isize, y as isize));
+                                'z'
+                            }
+                            _ => panic!("unknown char {}", c),
+                        };
+                        height_char as u8 - 'a' as u8
+                    })
+                    .collect();
+                width = row.len();
+                row
+            })
+            .flatten()
+            .collect();
+        let height = row_major.len() / width;
+        let size = Vector2::new(width as isize, height as isize);
+        Ok(Map::new(
+            row_major,
+            size,
+            start.expect("No start (S) found on map"),
+            end.expect("No end (E) found on Map"),
+        ))
+    }
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.size.y {
+            let start_index = (y * self.size.x) as usize;
+            let end_index = ((y + 1) * self.size.x) as usize;
+            self.row_major[start_index..end_index].iter().for_each(|h| {
+                let c = (h + 'a' as u8) as char;
+                write!(f, "{}", c).expect("Failed to write");
+            });
+            writeln!(f, "")?;
+        }
+        writeln!(f, "size: {}", self.size)?;
+        writeln!(f, "start: {}", self.start)?;
+        writeln!(f, "end: {}", self.end)?;
+        Ok(())
+    }
+}
+
+impl aoc_graph::Graph for Map {
+    type Node = Vector2;
+
+    fn neighbours(&self, loc: Vector2) -> Vec<(Vector2, usize)> {
+        Map::neighbours(self, &loc)
+    }
+}
+
+// The map with every step reversed, searching it from E finds the way down to all locations
+struct Descent<'a>(&'a Map);
+
+impl<'a> aoc_graph::Graph for Descent<'a> {
+    type Node = Vector2;
+
+    fn neighbours(&self, loc: Vector2) -> Vec<(Vector2, usize)> {
+        let map = self.0;
+        let height = map.height(&loc);
+        map.adjacent(&loc)
+            .filter_map(|n| Some((n, map.rules.cost(map.height(&n), height)?)))
+            .collect()
+    }
+}
+
+// Lowest cost from every location to E, None where E can not be reached
+struct DistanceField {
+    row_major: Vec<Option<usize>>,
+    size: Vector2,
+}
+
+impl DistanceField {
+    fn new(map: &Map) -> DistanceField {
+        let tree = aoc_graph::dijkstra(&Descent(map), map.end);
+        let row_major = (0..map.row_major.len())
+            .map(|i| tree.cost(map.loc_at(i)))
+            .collect();
+        DistanceField {
+            row_major,
+            size: map.size,
+        }
+    }
+
+    fn cost(&self, loc: &Vector2) -> Option<usize> {
+        self.row_major[(loc.y * self.size.x + loc.x) as usize]
+    }
+
+    // The location of a height closest to E, and the cost from there
+    fn closest(&self, map: &Map, height: u8) -> Option<(usize, Vector2)> {
+        map.row_major
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| **h == height)
+            .filter_map(|(i, _)| Some((self.row_major[i]?, map.loc_at(i))))
+            .min()
+    }
+}
+
+// Cost modulo 10, or . where E can not be reached
+impl fmt::Display for DistanceField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.row_major.chunks(self.size.x as usize) {
+            for steps in row.iter() {
+                match steps {
+                    Some(steps) => write!(f, "{}", steps % 10)?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn shortest_path(map: &Map, start: &Vector2) -> Option<Path<Vector2>> {
+    shortest_path_to(map, start, &map.end)
+}
+
+fn shortest_path_to(map: &Map, start: &Vector2, end: &Vector2) -> Option<Path<Vector2>> {
+    aoc_graph::astar(map, *start, *end, |loc| map.rules.min_cost(&loc, end))
+}
+
+// Regions of locations that can all reach each other under the rules of a map
+struct Connectivity {
+    regions: Vec<Vec<Vector2>>,
+    region_of: HashMap<Vector2, usize>,
+}
+
+impl Connectivity {
+    fn new(map: &Map) -> Connectivity {
+        let locs = (0..map.row_major.len()).map(|i| map.loc_at(i));
+        let regions = aoc_graph::strongly_connected_components(map, locs);
+        let region_of = regions
+            .iter()
+            .enumerate()
+            .flat_map(|(i, region)| region.iter().map(move |loc| (*loc, i)))
+            .collect();
+        Connectivity { regions, region_of }
+    }
+
+    fn region(&self, loc: &Vector2) -> &[Vector2] {
+        &self.regions[self.region_of[loc]]
+    }
+
+    fn largest(&self) -> &[Vector2] {
+        self.regions.iter().max_by_key(|r| r.len()).unwrap()
+    }
+}
+
+// Why a part has no answer
+#[derive(Debug, PartialEq, Eq)]
+enum Unreachable {
+    Start {
+        start: Vector2,
+        end: Vector2,
+        reachable: usize,
+    },
+    Height {
+        height: u8,
+        count: usize,
+        end: Vector2,
+    },
+}
+
+impl fmt::Display for Unreachable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unreachable::Start {
+                start,
+                end,
+                reachable,
+            } => write!(
+                f,
+                "no path from {} to E at {}, only {} locations can be reached",
+                start, end, reachable
+            ),
+            Unreachable::Height { height, count, end } => write!(
+                f,
+                "none of the {} locations of height {} can reach E at {}",
+                count,
+                (height + b'a') as char,
+                end
+            ),
+        }
+    }
+}
+
+fn answer(result: &Result<usize, Unreachable>) -> String {
+    match result {
+        Ok(steps) => steps.to_string(),
+        Err(e) => format!("unreachable, {}", e),
+    }
+}
+
+fn cost_from_start(map: &Map) -> Result<usize, Unreachable> {
+    match shortest_path(map, &map.start) {
+        Some(path) => Ok(path.cost),
+        None => Err(Unreachable::Start {
+            start: map.start,
+            end: map.end,
+            reachable: aoc_graph::bfs(map, map.start).len(),
+        }),
+    }
+}
+
+fn cost_from_lowest(map: &Map, field: &DistanceField) -> Result<usize, Unreachable> {
+    match field.closest(map, 0) {
+        Some((steps, _)) => Ok(steps),
+        None => Err(Unreachable::Height {
+            height: 0,
+            count: map.row_major.iter().filter(|h| **h == 0).count(),
+            end: map.end,
+        }),
+    }
+}
+
+fn solve_part1(input: &str) -> Result<usize, Unreachable> {
+    let map = Map::from_str(input).unwrap();
+
+    println!("{}", map);
+
+    cost_from_start(&map)
+}
+
+fn solve_part2(input: &str) -> Result<usize, Unreachable> {
+    let map = Map::from_str(input).unwrap();
+
+    let field = DistanceField::new(&map);
+    cost_from_lowest(&map, &field)
+}
+
+struct Repl {
+    map: Map,
+    field: DistanceField,
+    connectivity: Connectivity,
+}
+
+impl Repl {
+    fn new(input: &str) -> Repl {
+        let map = Map::from_str(input).unwrap();
+        let field = DistanceField::new(&map);
+        let connectivity = Connectivity::new(&map);
+        Repl {
+            map,
+            field,
+            connectivity,
+        }
+    }
+
+    // A location is S, E or x,y
+    fn loc(&self, args: &[&str], index: usize, name: &str) -> Result<Vector2, String> {
+        let loc = match args.get(index) {
+            Some(&"S") => self.map.start,
+            Some(&"E") => self.map.end,
+            _ => {
+                let (x, y) = aoc_repl::arg_pair::<isize>(args, index, name)?;
+                Vector2::new(x, y)
+            }
+        };
+        if self.map.is_valid_loc(&loc) {
+            Ok(loc)
+        } else {
+            Err(format!("{} is outside of the map", loc))
+        }
+    }
+
+    // Like loc, but also accepts a height a-z meaning all locations of that height
+    fn locs(&self, args: &[&str], index: usize, name: &str) -> Result<Vec<Vector2>, String> {
+        match args.get(index).map(|a| a.as_bytes()) {
+            Some(&[c]) if c.is_ascii_lowercase() => Ok(self
+                .map
+                .row_major
+                .iter()
+                .enumerate()
+                .filter(|(_, h)| **h == c - b'a')
+                .map(|(i, _)| self.map.loc_at(i))
+                .collect()),
+            _ => Ok(vec![self.loc(args, index, name)?]),
+        }
+    }
+
+    // Shortest path from any of the from locations to E or to, or the unreachable destination
+    fn best_path(&self, args: &[&str]) -> Result<Result<Path<Vector2>, Vector2>, String> {
+        let starts = self.locs(args, 0, "from")?;
+        let end = if args.len() > 1 {
+            self.loc(args, 1, "to")?
+        } else {
+            self.map.end
+        };
+        let best = starts
+            .iter()
+            .filter_map(|start| shortest_path_to(&self.map, start, &end))
+            .min_by_key(|path| (path.cost, path.nodes[0]));
+        Ok(best.ok_or(end))
+    }
+
+    // Changes the rules from option value pairs, like climb 2 or diagonals on, and reset
+    // that goes back to the puzzle rules
+    fn set_rules(&mut self, args: &[&str]) -> Result<(), String> {
+        let mut rules = self.map.rules;
+        let mut words = args.iter();
+        while let Some(&option) = words.next() {
+            if option == "reset" {
+                rules = Rules::puzzle();
+                continue;
+            }
+            let value = *words
+                .next()
+                .ok_or(format!("Missing argument <{}>", option))?;
+            let limit = || match value {
+                "any" => Ok(None),
+                _ => aoc_repl::arg::<u8>(&[value], 0, option).map(Some),
+            };
+            match option {
+                "diagonals" => {
+                    rules.diagonals = match value {
+                        "on" => true,
+                        "off" => false,
+                        _ => {
+                            return Err(format!(
+                                "Expected on or off for diagonals, got '{}'",
+                                value
+                            ))
+                        }
+                    }
+                }
+                "climb" => rules.max_climb = limit()?,
+                "descent" => rules.max_descent = limit()?,
+                "step" => rules.step_cost = aoc_repl::arg(&[value], 0, option)?,
+                "climbcost" => rules.climb_cost = aoc_repl::arg(&[value], 0, option)?,
+                "descentcost" => rules.descent_cost = aoc_repl::arg(&[value], 0, option)?,
+                _ => return Err(format!("Unknown rule '{}'", option)),
+            }
+        }
+        self.map.rules = rules;
+        self.field = DistanceField::new(&self.map);
+        self.connectivity = Connectivity::new(&self.map);
+        Ok(())
+    }
+
+    fn describe(&self, cost: usize, steps: usize, from: &Vector2) -> String {
+        if self.map.rules.counts_steps() {
+            format!("{} steps from {}", steps, from)
+        } else {
+            format!("{} steps costing {} from {}", steps, cost, from)
+        }
+    }
+}
+
+impl aoc_repl::Introspect for Repl {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![
+            ("print", "", "Print the height map"),
+            ("height", "<loc>", "Print the height at S, E or x,y"),
+            ("show", "<loc>", "Print the height map with a location marked"),
+            ("neighbours", "<loc>", "Print the locations reachable in one step"),
+            (
+                "path",
+                "<from> [to]",
+                "Print the fewest steps from a location, or any location of a height a-z, to E or another location",
+            ),
+            ("route", "<from> [to]", "Print the path command's route with arrows"),
+            (
+                "elevation",
+                "<from> [to]",
+                "Print the path command's route over heights shaded in grey",
+            ),
+            ("distances", "", "Print the lowest cost to E from every location, modulo 10"),
+            ("closest", "<height>", "Print the location of a height a-z closest to E"),
+            ("regions", "", "Print how the map splits into regions that can all reach each other"),
+            ("region", "<loc>", "Print the height map with the region of a location marked"),
+            ("reaching", "<height>", "Print how many locations of a height a-z can reach E"),
+            (
+                "rules",
+                "[<rule> <value>]...",
+                "Print or change the rules: diagonals on|off, climb|descent <n>|any, step|climbcost|descentcost <n>, reset",
+            ),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "print" => Ok(format!("{}", self.map)),
+            "height" => {
+                let loc = self.loc(args, 0, "loc")?;
+                Ok(((self.map.height(&loc) + b'a') as char).to_string())
+            }
+            "show" => {
+                let loc = self.loc(args, 0, "loc")?;
+                Ok(format!("{:?}", DebugMap::new(&self.map, &loc)))
+            }
+            "neighbours" => {
+                let loc = self.loc(args, 0, "loc")?;
+                Ok(self
+                    .map
+                    .neighbours(&loc)
+                    .iter()
+                    .map(|(n, _)| n.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" "))
+            }
+            "path" => match self.best_path(args)? {
+                Ok(path) => Ok(self.describe(path.cost, path.nodes.len() - 1, &path.nodes[0])),
+                Err(end) => Ok(format!("No path to {}", end)),
+            },
+            "route" | "elevation" => match self.best_path(args)? {
+                Ok(path) if command == "route" => Ok(format!(
+                    "{:?}",
+                    DebugMap::with_route(&self.map, &path.nodes)
+                )),
+                Ok(path) => Ok(format!(
+                    "{:?}",
+                    DebugMap::with_route(&self.map, &path.nodes).with_elevation()
+                )),
+                Err(end) => Ok(format!("No path to {}", end)),
+            },
+            "distances" => Ok(format!("{}", self.field)),
+            "regions" => Ok(format!(
+                "{} regions, the largest has {} locations and E is in one of {}",
+                self.connectivity.regions.len(),
+                self.connectivity.largest().len(),
+                self.connectivity.region(&self.map.end).len()
+            )),
+            "region" => {
+                let loc = self.loc(args, 0, "loc")?;
+                let region = self.connectivity.region(&loc);
+                let mut text = format!("{:?}", DebugMap::with_region(&self.map, region));
+                text.push_str(&format!("{} locations", region.len()));
+                Ok(text)
+            }
+            "reaching" => {
+                let height = match args.first().map(|a| a.as_bytes()) {
+                    Some(&[c]) if c.is_ascii_lowercase() => c - b'a',
+                    _ => return Err("Expected a height a-z".to_string()),
+                };
+                let locs: Vec<Vector2> = (0..self.map.row_major.len())
+                    .map(|i| self.map.loc_at(i))
+                    .filter(|loc| self.map.height(loc) == height)
+                    .collect();
+                let reaching = locs
+                    .iter()
+                    .filter(|loc| self.field.cost(loc).is_some())
+                    .count();
+                Ok(format!("{} of {} reach E", reaching, locs.len()))
+            }
+            "rules" => {
+                self.set_rules(args)?;
+                Ok(format!("{}", self.map.rules))
+            }
+            "closest" => {
+                let height = match args.first().map(|a| a.as_bytes()) {
+                    Some(&[c]) if c.is_ascii_lowercase() => c - b'a',
+                    _ => return Err("Expected a height a-z".to_string()),
+                };
+                match self.field.closest(&self.map, height) {
+                    Some((cost, loc)) if self.map.rules.counts_steps() => {
+                        Ok(format!("{} steps from {}", cost, loc))
+                    }
+                    Some((cost, loc)) => Ok(format!("Costs {} from {}", cost, loc)),
+                    None => Ok("No location of that height reaches E".to_string()),
+                }
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        cost_from_start(&self.map)
+            .map(|cost| cost.to_string())
+            .map_err(|e| e.to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        cost_from_lowest(&self.map, &self.field)
+            .map(|cost| cost.to_string())
+            .map_err(|e| e.to_string())
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
+    let filename = args.get(1).expect(
+        format!(
+            "Usage: {} input-filename [--repl|--route [elevation]]",
+            PACKAGE_NAME.unwrap()
+        )
+        .as_str(),
+    );
+
+    println!("Reading input from {}", filename);
+    let input = fs::read_to_string(filename).expect("Failed to read file");
+
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
+    println!("Answer 1: {}", answer(&answer1));
+    println!("Stats 1: {}", stats1);
+
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
+    println!("Answer 2: {}", answer(&answer2));
+    println!("Stats 2: {}", stats2);
+
+    if args.get(2).map(|a| a.as_str()) == Some("--route") {
+        let map = Map::from_str(&input).unwrap();
+        if let Some(path) = shortest_path(&map, &map.start) {
+            let debug_map = DebugMap::with_route(&map, &path.nodes);
+            if args.get(3).map(|a| a.as_str()) == Some("elevation") {
+                println!("\n{:?}", debug_map.with_elevation());
+            } else {
+                println!("\n{:?}", debug_map);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_day12 {
+    use super::*;
+
+    const EXAMPLE1: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+    #[test]
+    fn test1_1() {
+        assert_eq!(solve_part1(EXAMPLE1), Ok(31));
+    }
+
+    #[test]
+    fn test2_1() {
+        assert_eq!(solve_part2(EXAMPLE1), Ok(29));
+    }
+
+    #[test]
+    fn test_unreachable_1() {
+        assert_eq!(
+            solve_part1("SbE\nccc"),
+            Err(Unreachable::Start {
+                start: Vector2::new(0, 0),
+                end: Vector2::new(2, 0),
+                reachable: 5,
+            })
+        );
+        assert_eq!(
+            solve_part2("SbE\nccc"),
+            Err(Unreachable::Height {
+                height: 0,
+                count: 1,
+                end: Vector2::new(2, 0),
+            })
+        );
+    }
+
+    #[test]
+    fn test_connectivity_1() {
+        let map = Map::from_str(EXAMPLE1).unwrap();
+        let connectivity = Connectivity::new(&map);
+        assert_eq!(connectivity.regions.len(), 1);
+        assert_eq!(connectivity.region(&map.end).len(), 40);
+        let map = Map::from_str("SbE\nccc").unwrap();
+        let connectivity = Connectivity::new(&map);
+        assert_eq!(connectivity.regions.len(), 2);
+        assert_eq!(connectivity.largest().len(), 5);
+    }
+
+    #[test]
+    fn test2_distance_field_1() {
+        let map = Map::from_str(EXAMPLE1).unwrap();
+        let field = DistanceField::new(&map);
+        assert_eq!(field.cost(&map.start), Some(31));
+        assert_eq!(field.cost(&map.end), Some(0));
+        assert_eq!(field.closest(&map, 0), Some((29, Vector2::new(0, 4))));
+        assert_eq!(field.closest(&map, 25), Some((0, map.end)));
+    }
+
+    #[test]
+    fn test1_route_1() {
+        let map = Map::from_str(EXAMPLE1).unwrap();
+        let path = shortest_path(&map, &map.start).unwrap();
+        assert_eq!(path.nodes.len(), 32);
+        assert_eq!(path.nodes.first(), Some(&map.start));
+        let route = format!("{:?}", DebugMap::with_route(&map, &path.nodes));
+        assert_eq!(route.matches(|c| "<>^v".contains(c)).count(), 31);
+        assert_eq!(route.lines().nth(2).unwrap().chars().nth(5), Some('E'));
+    }
+
+    #[test]
+    fn test_rules_1() {
+        let rules = Rules::puzzle();
+        assert_eq!(rules.cost(3, 4), Some(1));
+        assert_eq!(rules.cost(3, 5), None);
+        assert_eq!(rules.cost(25, 0), Some(1));
+        let rules = Rules {
+            max_climb: Some(2),
+            max_descent: Some(3),
+            climb_cost: 2,
+            descent_cost: 1,
+            ..Rules::puzzle()
+        };
+        assert_eq!(rules.cost(3, 5), Some(5));
+        assert_eq!(rules.cost(5, 3), Some(3));
+        assert_eq!(rules.cost(5, 1), None);
+    }
+
+    #[test]
+    fn test_rules_2() {
+        let mut map = Map::from_str(EXAMPLE1).unwrap();
+        map.rules.diagonals = true;
+        let path = shortest_path(&map, &map.start).unwrap();
+        assert!(path.cost < 31);
+        assert_eq!(DistanceField::new(&map).cost(&map.start), Some(path.cost));
+
+        map.rules = Rules {
+            max_climb: None,
+            ..Rules::puzzle()
+        };
+        assert_eq!(shortest_path(&map, &map.start).unwrap().cost, 7);
+    }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert_eq!(
+            repl.execute("path", &["S"]),
+            Ok("31 steps from (0, 0)".to_string())
+        );
+        assert_eq!(
+            repl.execute("path", &["a"]),
+            Ok("29 steps from (0, 4)".to_string())
+        );
+        assert_eq!(
+            repl.execute("path", &["a", "5,3"]),
+            Ok("22 steps from (0, 4)".to_string())
+        );
+        assert_eq!(repl.execute("height", &["E"]), Ok("z".to_string()));
+        assert_eq!(
+            repl.execute("closest", &["a"]),
+            Ok("29 steps from (0, 4)".to_string())
+        );
+        assert!(repl.execute("height", &["8,5"]).is_err());
+        assert!(repl.execute("rules", &["climb", "x"]).is_err());
+        assert!(repl.execute("rules", &["climbcost", "1"]).is_ok());
+        assert_eq!(
+            repl.execute("path", &["S"]),
+            Ok("31 steps costing 56 from (0, 0)".to_string())
+        );
+        assert!(repl.execute("rules", &["reset"]).is_ok());
+        assert_eq!(
+            repl.execute("path", &["S"]),
+            Ok("31 steps from (0, 0)".to_string())
+        );
+        assert_eq!(repl.part1(), Ok("31".to_string()));
+        assert_eq!(repl.part2(), Ok("29".to_string()));
+        assert!(repl.execute("rules", &["climb", "0"]).is_ok());
+        assert!(repl.part1().is_err());
+    }
+
+    #[test]
+    fn test_repl_rules_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert!(repl.execute("rules", &["reset", "diagonals", "on"]).is_ok());
+        assert!(repl.map.rules.diagonals);
+        assert!(repl.execute("rules", &["reset", "climb", "2"]).is_ok());
+        assert!(!repl.map.rules.diagonals);
+        assert_eq!(repl.map.rules.max_climb, Some(2));
+        assert!(repl.execute("rules", &["climb", "1", "reset"]).is_ok());
+        assert_eq!(repl.map.rules.max_climb, Some(1));
+
+        assert!(repl.execute("rules", &["diagonals", "yes"]).is_err());
+        assert!(repl.execute("rules", &["diagonals"]).is_err());
+        assert!(repl.execute("rules", &["reset", "on"]).is_err());
+        // Nothing changes when any rule fails
+        assert!(repl
+            .execute("rules", &["diagonals", "on", "climb", "x"])
+            .is_err());
+        assert!(!repl.map.rules.diagonals);
+    }
+}
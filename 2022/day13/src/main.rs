@@ -183,12 +183,15 @@ impl fmt::Display for Value {
     }
 }
 
-fn solve_part1(input: &str) -> usize {
-    let values: Vec<Value> = input
+fn parse_packets(input: &str) -> Vec<Value> {
+    input
         .lines()
         .filter_map(|l| if l.len() > 0 { Some(l) } else { None })
         .map(|l| Value::from_str(l).unwrap())
-        .collect();
+        .collect()
+}
+
+fn ordered_pair_index_sum(values: &[Value]) -> usize {
     values
         .iter()
         .step_by(2)
@@ -206,13 +209,9 @@ fn solve_part1(input: &str) -> usize {
         .fold(0, |acc, i| acc + (i + 1))
 }
 
-fn solve_part2(input: &str) -> usize {
+fn decoder_key(packets: &[Value]) -> usize {
     let values = {
-        let mut values: Vec<Value> = input
-            .lines()
-            .filter_map(|l| if l.len() > 0 { Some(l) } else { None })
-            .map(|l| Value::from_str(l).unwrap())
-            .collect();
+        let mut values = packets.to_vec();
         values.sort_by(|l, r| l.partial_cmp(r).unwrap());
         values
     };
@@ -243,21 +242,110 @@ fn solve_part2(input: &str) -> usize {
     (div_indices[0] + 1) * (div_indices[1] + 2)
 }
 
+fn solve_part1(input: &str) -> usize {
+    ordered_pair_index_sum(&parse_packets(input))
+}
+
+fn solve_part2(input: &str) -> usize {
+    decoder_key(&parse_packets(input))
+}
+
+fn describe_order(left: &Value, right: &Value) -> String {
+    match left.partial_cmp(right) {
+        Some(Ordering::Less) => "In the right order".to_string(),
+        Some(Ordering::Greater) => "Not in the right order".to_string(),
+        _ => "Undecided".to_string(),
+    }
+}
+
+struct Repl {
+    packets: Vec<Value>,
+}
+
+impl Repl {
+    fn new(input: &str) -> Repl {
+        Repl {
+            packets: parse_packets(input),
+        }
+    }
+}
+
+impl aoc_repl::Introspect for Repl {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![
+            (
+                "pair",
+                "<n>",
+                "Whether pair n of the input, counted from 1, is in the right order",
+            ),
+            (
+                "compare",
+                "<left> <right>",
+                "Whether two packets are in the right order",
+            ),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "pair" => {
+                let n: usize = aoc_repl::arg(args, 0, "n")?;
+                let pair_count = self.packets.len() / 2;
+                if n == 0 || n > pair_count {
+                    return Err(format!("No pair {}, there are {}", n, pair_count));
+                }
+                let (left, right) = (&self.packets[2 * n - 2], &self.packets[2 * n - 1]);
+                Ok(format!(
+                    "{}\n{}\n{}",
+                    left,
+                    right,
+                    describe_order(left, right)
+                ))
+            }
+            "compare" => {
+                let packet = |index: usize, name: &str| {
+                    let s: String = aoc_repl::arg(args, index, name)?;
+                    Value::from_str(&s).map_err(|_| format!("Failed to parse packet from '{}'", s))
+                };
+                let (left, right) = (packet(0, "left")?, packet(1, "right")?);
+                Ok(describe_order(&left, &right))
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(ordered_pair_index_sum(&self.packets).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(decoder_key(&self.packets).to_string())
+    }
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
     const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
     let filename = args
         .get(1)
-        .expect(format!("Usage: {} input-filename", PACKAGE_NAME.unwrap()).as_str());
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
 
     println!("Reading input from {}", filename);
     let input = fs::read_to_string(filename).expect("Failed to read file");
 
-    let answer1 = solve_part1(&input);
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
     println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
 
-    let answer2 = solve_part2(&input);
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
     println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
 }
 
 #[cfg(test)]
@@ -438,4 +526,26 @@ mod tests_day13 {
     fn test2_1() {
         assert_eq!(solve_part2(EXAMPLE1), 140);
     }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert_eq!(
+            repl.execute("compare", &["[1,1,3,1,1]", "[1,1,5,1,1]"]),
+            Ok("In the right order".to_string())
+        );
+        assert_eq!(
+            repl.execute("compare", &["[9]", "[[8,7,6]]"]),
+            Ok("Not in the right order".to_string())
+        );
+        assert_eq!(
+            repl.execute("pair", &["2"]),
+            Ok("[[1],[2,3,4]]\n[[1],4]\nIn the right order".to_string())
+        );
+        assert!(repl.execute("pair", &["0"]).is_err());
+        assert!(repl.execute("pair", &["9"]).is_err());
+        assert_eq!(repl.part1(), Ok("13".to_string()));
+        assert_eq!(repl.part2(), Ok("140".to_string()));
+    }
 }
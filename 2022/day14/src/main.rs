@@ -110,15 +110,17 @@ fn find_line_at(lines: &[Line], p: &Vector2) -> bool {
         .is_some()
 }
 
-fn solve_part1(input: &str) -> usize {
-    let lines: Vec<Line> = input
+fn parse_rock(input: &str) -> Vec<Line> {
+    input
         .lines()
         .filter_map(|l| if l.len() > 0 { Some(l) } else { None })
         .map(|l| parse_lines(l))
         .map(|l| l.unwrap())
         .flatten()
-        .collect();
+        .collect()
+}
 
+fn count_resting_sand(lines: &[Line]) -> usize {
     let max_y = lines
         .iter()
         .map(|l| match l {
@@ -135,7 +137,7 @@ fn solve_part1(input: &str) -> usize {
     while sand.y <= max_y {
         if let Some(next_sand) = MOVES.iter().find_map(|m| {
             let candidate = sand.add(m);
-            if !sands.contains(&candidate) && !find_line_at(&lines[..], &candidate) {
+            if !sands.contains(&candidate) && !find_line_at(lines, &candidate) {
                 Some(candidate)
             } else {
                 None
@@ -151,15 +153,7 @@ fn solve_part1(input: &str) -> usize {
     sands.len()
 }
 
-fn solve_part2(input: &str) -> usize {
-    let lines: Vec<Line> = input
-        .lines()
-        .filter_map(|l| if l.len() > 0 { Some(l) } else { None })
-        .map(|l| parse_lines(l))
-        .map(|l| l.unwrap())
-        .flatten()
-        .collect();
-
+fn count_sand_until_blocked(lines: &[Line]) -> usize {
     let max_y = lines
         .iter()
         .map(|l| match l {
@@ -179,7 +173,7 @@ fn solve_part2(input: &str) -> usize {
         if let Some(next_sand) = MOVES.iter().find_map(|m| {
             let candidate = sand.add(m);
             if !sands.contains(&candidate)
-                && !find_line_at(&lines[..], &candidate)
+                && !find_line_at(lines, &candidate)
                 && candidate.y < floor
             {
                 Some(candidate)
@@ -200,21 +194,84 @@ fn solve_part2(input: &str) -> usize {
     sands.len()
 }
 
+fn solve_part1(input: &str) -> usize {
+    count_resting_sand(&parse_rock(input))
+}
+
+fn solve_part2(input: &str) -> usize {
+    count_sand_until_blocked(&parse_rock(input))
+}
+
+struct Repl {
+    lines: Vec<Line>,
+}
+
+impl Repl {
+    fn new(input: &str) -> Repl {
+        Repl {
+            lines: parse_rock(input),
+        }
+    }
+}
+
+impl aoc_repl::Introspect for Repl {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![
+            ("at", "<x,y>", "Whether there is rock at x,y"),
+            (
+                "rock",
+                "<x,y -> x,y ...>",
+                "Parse a path of rock into lines",
+            ),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "at" => {
+                let (x, y) = aoc_repl::arg_pair::<isize>(args, 0, "x,y")?;
+                Ok(find_line_at(&self.lines, &Vector2::new(x, y)).to_string())
+            }
+            "rock" => {
+                let lines = parse_lines(&args.join(" ")).map_err(|e| e.to_string())?;
+                Ok(format!("{:?}", lines))
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(count_resting_sand(&self.lines).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(count_sand_until_blocked(&self.lines).to_string())
+    }
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
     const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
     let filename = args
         .get(1)
-        .expect(format!("Usage: {} input-filename", PACKAGE_NAME.unwrap()).as_str());
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
 
     println!("Reading input from {}", filename);
     let input = fs::read_to_string(filename).expect("Failed to read file");
 
-    let answer1 = solve_part1(&input);
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
     println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
 
-    let answer2 = solve_part2(&input);
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
     println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
 }
 
 #[cfg(test)]
@@ -269,4 +326,16 @@ mod tests_day14 {
     fn test2_1() {
         assert_eq!(solve_part2(EXAMPLE1), 93);
     }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert!(repl.execute("rock", &["498,4", "->", "498,6"]).is_ok());
+        assert!(repl.execute("rock", &["498"]).is_err());
+        assert_eq!(repl.execute("at", &["498,5"]), Ok("true".to_string()));
+        assert_eq!(repl.execute("at", &["500,5"]), Ok("false".to_string()));
+        assert_eq!(repl.part1(), Ok("24".to_string()));
+        assert_eq!(repl.part2(), Ok("93".to_string()));
+    }
 }
@@ -4,7 +4,8 @@
 extern crate lazy_static;
 extern crate regex;
 
-use std::cmp::Ordering;
+use aoc_interval::{Interval, IntervalSet};
+use std::collections::HashSet;
 use std::env;
 use std::fmt;
 use std::fs;
@@ -35,61 +36,6 @@ impl fmt::Debug for Vector2 {
     }
 }
 
-#[derive(Eq, PartialEq)]
-struct Range {
-    min: isize,
-    max: isize,
-}
-
-impl Range {
-    fn new(min: isize, max: isize) -> Range {
-        Range { min, max }
-    }
-
-    fn from_center_and_dist(center: isize, dist: isize) -> Range {
-        Range {
-            min: center - dist,
-            max: center + dist,
-        }
-    }
-
-    // min1--min2 max1
-    fn intersection(&self, other: &Range) -> Option<Range> {
-        let i = Range {
-            min: self.min.max(other.min),
-            max: self.max.min(other.max),
-        };
-        if i.min <= i.max {
-            Some(i)
-        } else {
-            None
-        }
-    }
-}
-
-impl PartialOrd for Range {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for Range {
-    fn cmp(&self, other: &Self) -> Ordering {
-        let min_cmp = self.min.cmp(&other.min);
-        if min_cmp == Ordering::Equal {
-            self.max.cmp(&other.max)
-        } else {
-            min_cmp
-        }
-    }
-}
-
-impl fmt::Debug for Range {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "[{}, {}]", self.min, self.max)
-    }
-}
-
 #[derive(Clone)]
 struct SensorWithBeacon {
     sensor: Vector2,
@@ -109,10 +55,13 @@ impl SensorWithBeacon {
         diff.x.abs() + diff.y.abs()
     }
 
-    fn range_on_row(&self, y: isize) -> Option<Range> {
+    fn range_on_row(&self, y: isize) -> Option<Interval<isize>> {
         let dist = (self.sensor.y - y).abs() - self.manhattan_distance();
         if dist <= 0 {
-            Some(Range::from_center_and_dist(self.sensor.x, dist.abs()))
+            Some(Interval::inclusive(
+                self.sensor.x + dist,
+                self.sensor.x - dist,
+            ))
         } else {
             None
         }
@@ -151,31 +100,16 @@ fn parse_sensors(input: &str) -> Vec<SensorWithBeacon> {
 }
 
 fn count_known_locations(sensor_beacons: &[SensorWithBeacon], row: isize) -> usize {
-    let ranges: Vec<Range> = {
-        let mut ranges: Vec<Range> = sensor_beacons
-            .iter()
-            .filter_map(|s| {
-                if let Some(r) = s.range_on_row(row) {
-                    Some(r)
-                } else {
-                    None
-                }
-            })
-            .collect();
-        ranges.sort_unstable();
-        ranges
-    };
-    if ranges.len() == 0 {
-        return 0;
-    }
-
-    let mut count = 0;
-    let mut prev_max = ranges[0].min - 1;
-    for r in ranges {
-        count += (r.max - r.min.max(prev_max)).max(0) as usize;
-        prev_max = r.max.max(prev_max);
-    }
-    count as usize
+    let covered: IntervalSet<isize> = sensor_beacons
+        .iter()
+        .filter_map(|s| s.range_on_row(row))
+        .collect();
+    let beacons_on_row: HashSet<isize> = sensor_beacons
+        .iter()
+        .filter(|s| s.beacon.y == row && covered.contains(s.beacon.x))
+        .map(|s| s.beacon.x)
+        .collect();
+    covered.len() as usize - beacons_on_row.len()
 }
 
 fn find_unknown_x_on_row(
@@ -183,44 +117,14 @@ fn find_unknown_x_on_row(
     row: isize,
     max_x: isize,
 ) -> Option<isize> {
-    let row_range = Range::new(0, max_x);
-    let ranges: Vec<Range> = {
-        let mut ranges: Vec<Range> = sensor_beacons
-            .iter()
-            .filter_map(|s| {
-                if let Some(r) = s.range_on_row(row) {
-                    Some(r)
-                } else {
-                    None
-                }
-            })
-            .filter_map(|r| r.intersection(&row_range))
-            .collect();
-        ranges.sort_unstable();
-        ranges
-    };
-    if ranges.len() == 0 {
-        return Some(0);
-    }
-
-    let first = &ranges.first().unwrap();
-    if first.min > 0 {
-        return Some(ranges[0].min);
-    }
-
-    let mut r_max_x = first.max;
-    for r in &ranges {
-        if (r_max_x + 1) < r.min {
-            return Some(r_max_x + 1);
-        }
-        r_max_x = r_max_x.max(r.max);
-    }
-
-    if r_max_x < max_x {
-        return Some(r_max_x + 1);
-    }
-
-    return None;
+    let covered: IntervalSet<isize> = sensor_beacons
+        .iter()
+        .filter_map(|s| s.range_on_row(row))
+        .collect();
+    IntervalSet::from(Interval::inclusive(0, max_x))
+        .difference(&covered)
+        .first()
+        .map(|i| i.start())
 }
 
 fn find_unknown_pos(sensor_beacons: &[SensorWithBeacon], max_xy: isize) -> Option<Vector2> {
@@ -244,6 +148,13 @@ fn find_unknown_pos(sensor_beacons: &[SensorWithBeacon], max_xy: isize) -> Optio
     None
 }
 
+fn tuning_frequency(sensors_beacons: &[SensorWithBeacon], max_xy: isize) -> usize {
+    if let Some(pos) = find_unknown_pos(sensors_beacons, max_xy) {
+        return (pos.x * 4000000 + pos.y) as usize;
+    }
+    0
+}
+
 fn solve_part1(input: &str) -> usize {
     let sensors_beacons = parse_sensors(input);
     count_known_locations(&sensors_beacons[..], 2000000)
@@ -251,10 +162,96 @@ fn solve_part1(input: &str) -> usize {
 
 fn solve_part2(input: &str) -> usize {
     let sensors_beacons = parse_sensors(input);
-    if let Some(pos) = find_unknown_pos(&sensors_beacons[..], 4000000) {
-        return (pos.x * 4000000 + pos.y) as usize;
+    tuning_frequency(&sensors_beacons, 4000000)
+}
+
+struct Repl {
+    sensor_beacons: Vec<SensorWithBeacon>,
+}
+
+impl Repl {
+    fn new(input: &str) -> Repl {
+        Repl {
+            sensor_beacons: parse_sensors(input),
+        }
+    }
+}
+
+impl aoc_repl::Introspect for Repl {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![
+            ("print", "", "Print all sensors with their closest beacon"),
+            (
+                "sensor",
+                "<index>",
+                "Print a sensor and its beacon distance",
+            ),
+            (
+                "ranges",
+                "<y>",
+                "Print the x ranges covered by each sensor on row y",
+            ),
+            (
+                "row",
+                "<y>",
+                "Count locations on row y where there can be no beacon",
+            ),
+            (
+                "unknown",
+                "<max_xy>",
+                "Find the uncovered location within 0..=max_xy",
+            ),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "print" => Ok(self
+                .sensor_beacons
+                .iter()
+                .enumerate()
+                .map(|(i, s)| format!("{:2}: {:?}", i, s))
+                .collect::<Vec<String>>()
+                .join("\n")),
+            "sensor" => {
+                let index: usize = aoc_repl::arg(args, 0, "index")?;
+                let s = self
+                    .sensor_beacons
+                    .get(index)
+                    .ok_or(format!("No sensor {}", index))?;
+                Ok(format!("{:?} distance {}", s, s.manhattan_distance()))
+            }
+            "ranges" => {
+                let y: isize = aoc_repl::arg(args, 0, "y")?;
+                Ok(self
+                    .sensor_beacons
+                    .iter()
+                    .filter_map(|s| Some(format!("{:?}: {:?}", s.sensor, s.range_on_row(y)?)))
+                    .collect::<Vec<String>>()
+                    .join("\n"))
+            }
+            "row" => {
+                let y: isize = aoc_repl::arg(args, 0, "y")?;
+                Ok(count_known_locations(&self.sensor_beacons[..], y).to_string())
+            }
+            "unknown" => {
+                let max_xy: isize = aoc_repl::arg(args, 0, "max_xy")?;
+                match find_unknown_pos(&self.sensor_beacons[..], max_xy) {
+                    Some(pos) => Ok(format!("{:?}", pos)),
+                    None => Ok("None".to_string()),
+                }
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(count_known_locations(&self.sensor_beacons, 2000000).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(tuning_frequency(&self.sensor_beacons, 4000000).to_string())
     }
-    0
 }
 
 fn main() {
@@ -262,16 +259,24 @@ fn main() {
     const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
     let filename = args
         .get(1)
-        .expect(format!("Usage: {} input-filename", PACKAGE_NAME.unwrap()).as_str());
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
 
     println!("Reading input from {}", filename);
     let input = fs::read_to_string(filename).expect("Failed to read file");
 
-    let answer1 = solve_part1(&input);
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
     println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
 
-    let answer2 = solve_part2(&input);
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
     println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
 }
 
 #[cfg(test)]
@@ -313,13 +318,13 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3
     #[test]
     fn test1_range_on_row_1() {
         let s = SensorWithBeacon::new(&Vector2::new(0, 3), &Vector2::new(0, 0));
-        assert_eq!(s.range_on_row(0), Some(Range::new(0, 0)));
-        assert_eq!(s.range_on_row(1), Some(Range::new(-1, 1)));
-        assert_eq!(s.range_on_row(2), Some(Range::new(-2, 2)));
-        assert_eq!(s.range_on_row(3), Some(Range::new(-3, 3)));
-        assert_eq!(s.range_on_row(4), Some(Range::new(-2, 2)));
-        assert_eq!(s.range_on_row(5), Some(Range::new(-1, 1)));
-        assert_eq!(s.range_on_row(6), Some(Range::new(0, 0)));
+        assert_eq!(s.range_on_row(0), Some(Interval::inclusive(0, 0)));
+        assert_eq!(s.range_on_row(1), Some(Interval::inclusive(-1, 1)));
+        assert_eq!(s.range_on_row(2), Some(Interval::inclusive(-2, 2)));
+        assert_eq!(s.range_on_row(3), Some(Interval::inclusive(-3, 3)));
+        assert_eq!(s.range_on_row(4), Some(Interval::inclusive(-2, 2)));
+        assert_eq!(s.range_on_row(5), Some(Interval::inclusive(-1, 1)));
+        assert_eq!(s.range_on_row(6), Some(Interval::inclusive(0, 0)));
 
         assert_eq!(s.range_on_row(-1), None);
         assert_eq!(s.range_on_row(7), None);
@@ -344,21 +349,35 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3
     #[test]
     fn test2_range_intersection_1() {
         assert_eq!(
-            Range::new(0, 2).intersection(&Range::new(1, 3)),
-            Some(Range::new(1, 2))
+            Interval::inclusive(0, 2).intersection(&Interval::inclusive(1, 3)),
+            Some(Interval::inclusive(1, 2))
         );
     }
 
     #[test]
     fn test2_range_intersection_2() {
         assert_eq!(
-            Range::new(0, 2).intersection(&Range::new(2, 3)),
-            Some(Range::new(2, 2))
+            Interval::inclusive(0, 2).intersection(&Interval::inclusive(2, 3)),
+            Some(Interval::inclusive(2, 2))
         );
     }
 
     #[test]
     fn test2_range_intersection_3() {
-        assert_eq!(Range::new(0, 2).intersection(&Range::new(3, 3)), None);
+        assert_eq!(
+            Interval::inclusive(0, 2).intersection(&Interval::inclusive(3, 3)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert_eq!(repl.execute("row", &["10"]), Ok("26".to_string()));
+        assert_eq!(repl.execute("unknown", &["20"]), Ok("(14, 11)".to_string()));
+        assert!(repl.execute("sensor", &["14"]).is_err());
+        assert_eq!(repl.part1(), Ok(solve_part1(EXAMPLE1).to_string()));
+        assert_eq!(tuning_frequency(&repl.sensor_beacons, 20), 56000011);
     }
 }
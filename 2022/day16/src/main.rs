@@ -0,0 +1,974 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate lazy_static;
+extern crate regex;
+
+use aoc_graph::{AdjacencyGraph, AllPairs};
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::process;
+
+#[derive(PartialEq, Debug)]
+struct Valve<'a> {
+    name: &'a str,
+    flow: usize,
+    neighbours: Vec<&'a str>,
+}
+
+impl<'a> Valve<'a> {
+    fn new(name: &'a str, flow: usize, neighbours: Vec<&'a str>) -> Valve<'a> {
+        Valve {
+            name,
+            flow,
+            neighbours,
+        }
+    }
+
+    fn from_str(s: &'a str) -> Result<Valve<'a>, String> {
+        lazy_static! {
+            static ref RE: regex::Regex = regex::Regex::new(
+                r"^Valve ([A-Z]+) has flow rate=(\d+); (?:tunnels lead to valves|tunnel leads to valve) ([A-Z]+(?:, [A-Z]+)*)$"
+            )
+            .unwrap();
+        }
+        let captures = RE
+            .captures(s.trim_end())
+            .ok_or(format!("Failed to parse valve from '{}'", s))?;
+        assert_eq!(captures.len(), 4);
+        let name = captures.get(1).unwrap().as_str();
+        let flow: usize = captures
+            .get(2)
+            .unwrap()
+            .as_str()
+            .parse::<usize>()
+            .map_err(|e| format!("Failed to parse flow rate of {}: {}", name, e))?;
+        let neighbours: Vec<&'a str> = captures.get(3).unwrap().as_str().split(", ").collect();
+        Ok(Valve::new(name, flow, neighbours))
+    }
+}
+
+// Valve we start at, every valve has to be reachable from it
+const START: &str = "AA";
+
+fn parse_valves(input: &str) -> Result<HashMap<&str, Valve<'_>>, String> {
+    let mut valves: HashMap<&str, Valve> = HashMap::new();
+    let mut line_numbers: HashMap<&str, usize> = HashMap::new();
+    for (index, l) in input.lines().enumerate() {
+        if l.trim().is_empty() {
+            continue;
+        }
+        let line_number = index + 1;
+        let valve = Valve::from_str(l).map_err(|e| format!("Line {}: {}", line_number, e))?;
+        if let Some(other) = line_numbers.insert(valve.name, line_number) {
+            return Err(format!(
+                "Line {}: Valve {} is already defined on line {}",
+                line_number, valve.name, other
+            ));
+        }
+        valves.insert(valve.name, valve);
+    }
+
+    let mut names: Vec<&str> = valves.keys().copied().collect();
+    names.sort_by_key(|name| line_numbers[name]);
+    for name in names.iter().copied() {
+        for n in valves[name].neighbours.iter() {
+            let error = match valves.get(n) {
+                None => format!("Tunnel from {} leads to unknown valve {}", name, n),
+                Some(other) if !other.neighbours.contains(&name) => format!(
+                    "Tunnel from {} to {} has no tunnel back on line {}",
+                    name, n, line_numbers[n]
+                ),
+                Some(_) => continue,
+            };
+            return Err(format!("Line {}: {}", line_numbers[name], error));
+        }
+    }
+
+    if !valves.contains_key(START) {
+        return Err(format!("Start valve {} is not defined", START));
+    }
+    // Tunnels go both ways, so every valve reached from the start can reach all others
+    let mut reached: HashSet<&str> = HashSet::from([START]);
+    let mut stack: Vec<&str> = vec![START];
+    while let Some(name) = stack.pop() {
+        for n in valves[name].neighbours.iter() {
+            if reached.insert(n) {
+                stack.push(n);
+            }
+        }
+    }
+    if let Some(name) = names.iter().find(|name| !reached.contains(*name)) {
+        return Err(format!(
+            "Line {}: Valve {} can not be reached from {}",
+            line_numbers[name], name, START
+        ));
+    }
+    Ok(valves)
+}
+
+// Every valve as a node, with the shortest walk between any two of them
+struct Tunnels<'a> {
+    names: Vec<&'a str>,
+    indices: HashMap<&'a str, usize>,
+    paths: AllPairs,
+}
+
+impl<'a> Tunnels<'a> {
+    fn new(valves: &HashMap<&'a str, Valve<'a>>) -> Tunnels<'a> {
+        let mut names: Vec<&'a str> = valves.keys().copied().collect();
+        names.sort();
+        let indices: HashMap<&'a str, usize> =
+            names.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+        let mut graph = AdjacencyGraph::new(names.len());
+        for (i, name) in names.iter().enumerate() {
+            for n in valves[name].neighbours.iter() {
+                graph.add_edge(i, indices[n], 1);
+            }
+        }
+        Tunnels {
+            names,
+            indices,
+            paths: AllPairs::repeated_bfs(&graph),
+        }
+    }
+
+    fn distance(&self, from: &str, to: &str) -> usize {
+        self.paths
+            .distance(self.indices[from], self.indices[to])
+            .expect("Valves are not connected")
+    }
+
+    // Valves walked through going from one valve to another, not including from
+    fn path(&self, from: &str, to: &str) -> Vec<&'a str> {
+        let path = self
+            .paths
+            .path(self.indices[from], self.indices[to])
+            .expect("Valves are not connected");
+        path.iter().skip(1).map(|i| self.names[*i]).collect()
+    }
+}
+
+// The valves worth opening and where we start, with the minutes it takes to walk between them
+struct Network<'a> {
+    names: Vec<&'a str>,
+    flows: Vec<usize>,
+    distances: Vec<Vec<usize>>,
+    flowing_count: usize,
+    start: usize,
+    tunnels: Tunnels<'a>,
+}
+
+impl<'a> Network<'a> {
+    fn new(valves: &HashMap<&'a str, Valve<'a>>, start: &'a str) -> Network<'a> {
+        let mut names: Vec<&'a str> = valves
+            .values()
+            .filter(|v| v.flow > 0)
+            .map(|v| v.name)
+            .collect();
+        names.sort();
+        let flowing_count = names.len();
+        let start_index = match names.iter().position(|n| *n == start) {
+            Some(index) => index,
+            None => {
+                names.push(start);
+                names.len() - 1
+            }
+        };
+        let flows: Vec<usize> = names.iter().map(|n| valves[n].flow).collect();
+        let tunnels = Tunnels::new(valves);
+        let distances: Vec<Vec<usize>> = names
+            .iter()
+            .map(|from| names.iter().map(|to| tunnels.distance(from, to)).collect())
+            .collect();
+        Network {
+            names,
+            flows,
+            distances,
+            flowing_count,
+            start: start_index,
+            tunnels,
+        }
+    }
+
+    // Most pressure that can be released by opening exactly the valves of each bitmask
+    fn best_pressure_per_opened(&self, minutes: usize) -> Vec<usize> {
+        let mut best: Vec<usize> = vec![0; 1 << self.flowing_count];
+        self.visit(self.start, minutes, 0, 0, &mut best);
+        best
+    }
+
+    fn visit(
+        &self,
+        valve: usize,
+        minutes: usize,
+        opened: usize,
+        pressure: usize,
+        best: &mut [usize],
+    ) {
+        best[opened] = best[opened].max(pressure);
+        for next in 0..self.flowing_count {
+            let cost = self.distances[valve][next] + 1;
+            if opened & (1 << next) != 0 || cost >= minutes {
+                continue;
+            }
+            let minutes_left = minutes - cost;
+            self.visit(
+                next,
+                minutes_left,
+                opened | (1 << next),
+                pressure + minutes_left * self.flows[next],
+                best,
+            );
+        }
+    }
+
+    // Order to open some of the allowed valves in, that releases the most pressure
+    fn best_order(&self, minutes: usize, allowed: usize) -> (usize, Vec<usize>) {
+        let mut best: (usize, Vec<usize>) = (0, Vec::new());
+        let mut order: Vec<usize> = Vec::new();
+        self.visit_order(self.start, minutes, allowed, 0, &mut order, &mut best);
+        best
+    }
+
+    fn visit_order(
+        &self,
+        valve: usize,
+        minutes: usize,
+        allowed: usize,
+        pressure: usize,
+        order: &mut Vec<usize>,
+        best: &mut (usize, Vec<usize>),
+    ) {
+        if pressure > best.0 {
+            *best = (pressure, order.clone());
+        }
+        for next in 0..self.flowing_count {
+            let cost = self.distances[valve][next] + 1;
+            if allowed & (1 << next) == 0 || cost >= minutes {
+                continue;
+            }
+            let minutes_left = minutes - cost;
+            order.push(next);
+            self.visit_order(
+                next,
+                minutes_left,
+                allowed ^ (1 << next),
+                pressure + minutes_left * self.flows[next],
+                order,
+                best,
+            );
+            order.pop();
+        }
+    }
+}
+
+// Like best_pressure_per_opened, but for all subsets of each bitmask
+fn best_pressure_within_opened(network: &Network, minutes: usize) -> Vec<usize> {
+    let mut best = network.best_pressure_per_opened(minutes);
+    for bit in 0..network.flowing_count {
+        for opened in 0..best.len() {
+            if opened & (1 << bit) != 0 {
+                best[opened] = best[opened].max(best[opened ^ (1 << bit)]);
+            }
+        }
+    }
+    best
+}
+
+fn subsets(mask: usize) -> impl Iterator<Item = usize> {
+    let mut next = Some(mask);
+    std::iter::from_fn(move || {
+        let subset = next?;
+        next = if subset == 0 {
+            None
+        } else {
+            Some((subset - 1) & mask)
+        };
+        Some(subset)
+    })
+}
+
+// Agents work in parallel from the start valve, each opening valves the others leave closed.
+// Returns the most pressure released together, and the valves each agent may open for it.
+fn split_between_agents(network: &Network, minutes: usize, agents: usize) -> (usize, Vec<usize>) {
+    assert!(agents > 0, "At least one agent is needed to open valves");
+    let single = best_pressure_within_opened(network, minutes);
+    let mut combined: Vec<Vec<usize>> = vec![single.clone()];
+    for agent in 1..agents {
+        let previous = &combined[agent - 1];
+        let next = (0..single.len())
+            .map(|opened| {
+                subsets(opened)
+                    .map(|subset| previous[subset] + single[opened ^ subset])
+                    .max()
+                    .unwrap()
+            })
+            .collect();
+        combined.push(next);
+    }
+
+    let all = single.len() - 1;
+    let total = combined.last().unwrap()[all];
+    let mut allowed: Vec<usize> = Vec::new();
+    let mut opened = all;
+    for agent in (1..combined.len()).rev() {
+        let subset = subsets(opened)
+            .find(|subset| {
+                combined[agent - 1][*subset] + single[opened ^ subset] == combined[agent][opened]
+            })
+            .unwrap();
+        allowed.push(opened ^ subset);
+        opened = subset;
+    }
+    allowed.push(opened);
+    allowed.reverse();
+    (total, allowed)
+}
+
+fn max_pressure(network: &Network, minutes: usize, agents: usize) -> usize {
+    split_between_agents(network, minutes, agents).0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Action<'a> {
+    Move(&'a str),
+    Open(&'a str),
+}
+
+struct Minute<'a> {
+    open: Vec<&'a str>,
+    released: usize,
+    total: usize,
+    actions: Vec<Option<Action<'a>>>,
+}
+
+struct Schedule<'a> {
+    start: &'a str,
+    minutes: Vec<Minute<'a>>,
+}
+
+impl<'a> Schedule<'a> {
+    fn new(valves: &HashMap<&'a str, Valve<'a>>, minutes: usize, agents: usize) -> Schedule<'a> {
+        let network = Network::new(valves, START);
+        let (_, allowed) = split_between_agents(&network, minutes, agents);
+        let actions: Vec<Vec<Action>> = allowed
+            .iter()
+            .map(|allowed| {
+                let (_, order) = network.best_order(minutes, *allowed);
+                let mut position = network.names[network.start];
+                let mut actions: Vec<Action> = Vec::new();
+                for valve in order.iter().map(|v| network.names[*v]) {
+                    let path = network.tunnels.path(position, valve);
+                    actions.extend(path.iter().map(|n| Action::Move(n)));
+                    actions.push(Action::Open(valve));
+                    position = valve;
+                }
+                actions
+            })
+            .collect();
+
+        let mut open: Vec<&'a str> = Vec::new();
+        let mut total = 0;
+        let minutes = (0..minutes)
+            .map(|minute| {
+                let released: usize = open.iter().map(|n| valves[n].flow).sum();
+                total += released;
+                let minute = Minute {
+                    open: open.clone(),
+                    released,
+                    total,
+                    actions: actions.iter().map(|a| a.get(minute).copied()).collect(),
+                };
+                for action in minute.actions.iter() {
+                    if let Some(Action::Open(valve)) = action {
+                        open.push(valve);
+                    }
+                }
+                open.sort();
+                minute
+            })
+            .collect();
+        Schedule {
+            start: network.names[network.start],
+            minutes,
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.minutes.last().map(|m| m.total).unwrap_or(0)
+    }
+
+    // Valves each agent walks through, starting with the start valve
+    fn routes(&self) -> Vec<Vec<&'a str>> {
+        let agents = self.minutes.first().map(|m| m.actions.len()).unwrap_or(0);
+        (0..agents)
+            .map(|agent| {
+                let mut route = vec![self.start];
+                route.extend(self.minutes.iter().filter_map(|m| match m.actions[agent] {
+                    Some(Action::Move(valve)) => Some(valve),
+                    _ => None,
+                }));
+                route
+            })
+            .collect()
+    }
+}
+
+impl<'a> fmt::Display for Schedule<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, minute) in self.minutes.iter().enumerate() {
+            writeln!(f, "== Minute {} ==", i + 1)?;
+            match &minute.open[..] {
+                [] => writeln!(f, "No valves are open.")?,
+                [valve] => writeln!(
+                    f,
+                    "Valve {} is open, releasing {} pressure.",
+                    valve, minute.released
+                )?,
+                [first, second] => writeln!(
+                    f,
+                    "Valves {} and {} are open, releasing {} pressure.",
+                    first, second, minute.released
+                )?,
+                [rest @ .., last] => writeln!(
+                    f,
+                    "Valves {}, and {} are open, releasing {} pressure.",
+                    rest.join(", "),
+                    last,
+                    minute.released
+                )?,
+            }
+            for (agent, action) in minute.actions.iter().enumerate() {
+                let subject = match agent {
+                    0 => "You".to_string(),
+                    1 => "The elephant".to_string(),
+                    _ => format!("Elephant {}", agent),
+                };
+                let s = if agent == 0 { "" } else { "s" };
+                match action {
+                    Some(Action::Move(valve)) => {
+                        writeln!(f, "{} move{} to valve {}.", subject, s, valve)?
+                    }
+                    Some(Action::Open(valve)) => {
+                        writeln!(f, "{} open{} valve {}.", subject, s, valve)?
+                    }
+                    None => {}
+                }
+            }
+            writeln!(f)?;
+        }
+        write!(f, "Released {} pressure in total.", self.total())
+    }
+}
+
+fn solve_part1(input: &str) -> usize {
+    let valves = match parse_valves(input) {
+        Ok(valves) => valves,
+        Err(e) => panic!("Error: {}", e),
+    };
+    let network = Network::new(&valves, START);
+    max_pressure(&network, 30, 1)
+}
+
+fn solve_part2(input: &str) -> usize {
+    let valves = match parse_valves(input) {
+        Ok(valves) => valves,
+        Err(e) => panic!("Error: {}", e),
+    };
+    let network = Network::new(&valves, START);
+    max_pressure(&network, 26, 2)
+}
+
+const ROUTE_COLORS: [&str; 4] = ["red", "blue", "darkgreen", "orange"];
+
+// Graphviz graph of the tunnels, with valves sized by flow rate. Optionally with the
+// distances between the valves worth opening, and the routes of a schedule.
+fn to_dot(valves: &HashMap<&str, Valve>, distances: bool, schedule: Option<&Schedule>) -> String {
+    let mut names: Vec<&str> = valves.keys().copied().collect();
+    names.sort();
+    let max_flow = valves.values().map(|v| v.flow).max().unwrap_or(0).max(1);
+
+    let mut edge_colors: HashMap<(&str, &str), Vec<&str>> = HashMap::new();
+    let mut opened: HashMap<&str, &str> = HashMap::new();
+    if let Some(schedule) = schedule {
+        for (agent, route) in schedule.routes().iter().enumerate() {
+            let color = ROUTE_COLORS[agent % ROUTE_COLORS.len()];
+            for pair in route.windows(2) {
+                let key = (pair[0].min(pair[1]), pair[0].max(pair[1]));
+                let colors = edge_colors.entry(key).or_default();
+                if !colors.contains(&color) {
+                    colors.push(color);
+                }
+            }
+        }
+        for minute in schedule.minutes.iter() {
+            for (agent, action) in minute.actions.iter().enumerate() {
+                if let Some(Action::Open(valve)) = action {
+                    opened.insert(valve, ROUTE_COLORS[agent % ROUTE_COLORS.len()]);
+                }
+            }
+        }
+    }
+
+    let mut lines: Vec<String> = vec![
+        "graph valves {".to_string(),
+        "  node [shape=circle, style=filled, fontname=monospace];".to_string(),
+    ];
+    for name in names.iter() {
+        let flow = valves[name].flow;
+        let width = 0.5 + flow as f64 / max_flow as f64;
+        let fill = if flow > 0 { "gold" } else { "lightgrey" };
+        let border = match opened.get(name) {
+            Some(color) => format!(", color={}, penwidth=3", color),
+            None => String::new(),
+        };
+        lines.push(format!(
+            "  {} [label=\"{}\\n{}\", width={:.2}, fillcolor={}{}];",
+            name, name, flow, width, fill, border
+        ));
+    }
+    for name in names.iter() {
+        for n in valves[name].neighbours.iter().filter(|n| *name < **n) {
+            match edge_colors.get(&(*name, *n)) {
+                Some(colors) => lines.push(format!(
+                    "  {} -- {} [color=\"{}\", penwidth=3];",
+                    name,
+                    n,
+                    colors.join(":")
+                )),
+                None => lines.push(format!("  {} -- {};", name, n)),
+            }
+        }
+    }
+    if distances {
+        let network = Network::new(valves, START);
+        for (i, from) in network.names.iter().enumerate() {
+            for (j, to) in network.names.iter().enumerate().skip(i + 1) {
+                lines.push(format!(
+                    "  {} -- {} [label=\"{}\", style=dashed, color=grey, fontcolor=grey, constraint=false];",
+                    from, to, network.distances[i][j]
+                ));
+            }
+        }
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+struct Repl<'a> {
+    valves: HashMap<&'a str, Valve<'a>>,
+}
+
+impl<'a> Repl<'a> {
+    fn new(input: &'a str) -> Result<Repl<'a>, String> {
+        Ok(Repl {
+            valves: parse_valves(input)?,
+        })
+    }
+
+    fn valve(&self, args: &[&str]) -> Result<&Valve<'a>, String> {
+        let name: String = aoc_repl::arg(args, 0, "name")?;
+        self.valves
+            .get(name.as_str())
+            .ok_or(format!("No valve {}", name))
+    }
+
+    fn minutes_and_agents(&self, args: &[&str]) -> Result<(usize, usize), String> {
+        let minutes: usize = aoc_repl::arg_or(args, 0, "minutes", 30)?;
+        let agents: usize = aoc_repl::arg_or(args, 1, "agents", 1)?;
+        if agents == 0 {
+            return Err("At least one agent is needed to open valves".to_string());
+        }
+        Ok((minutes, agents))
+    }
+}
+
+impl<'a> aoc_repl::Introspect for Repl<'a> {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![
+            ("print", "", "Print all valves"),
+            (
+                "valve",
+                "<name>",
+                "Print a valve with its flow rate and tunnels",
+            ),
+            ("flowing", "", "Print the valves with a non-zero flow rate"),
+            (
+                "pressure",
+                "[minutes] [agents]",
+                "Print the most pressure that agents can release from AA",
+            ),
+            (
+                "explain",
+                "[minutes] [agents]",
+                "Print what each agent does every minute to release the most pressure",
+            ),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        let mut valves: Vec<&Valve> = self.valves.values().collect();
+        valves.sort_by_key(|v| v.name);
+        match command {
+            "print" => Ok(valves
+                .iter()
+                .map(|v| format!("{:?}", v))
+                .collect::<Vec<String>>()
+                .join("\n")),
+            "valve" => Ok(format!("{:?}", self.valve(args)?)),
+            "flowing" => Ok(valves
+                .iter()
+                .filter(|v| v.flow > 0)
+                .map(|v| format!("{} {}", v.name, v.flow))
+                .collect::<Vec<String>>()
+                .join("\n")),
+            "pressure" => {
+                let (minutes, agents) = self.minutes_and_agents(args)?;
+                let network = Network::new(&self.valves, START);
+                Ok(max_pressure(&network, minutes, agents).to_string())
+            }
+            "explain" => {
+                let (minutes, agents) = self.minutes_and_agents(args)?;
+                Ok(Schedule::new(&self.valves, minutes, agents).to_string())
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        let network = Network::new(&self.valves, START);
+        Ok(max_pressure(&network, 30, 1).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        let network = Network::new(&self.valves, START);
+        Ok(max_pressure(&network, 26, 2).to_string())
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
+    let filename = args.get(1).expect(
+        format!(
+            "Usage: {} input-filename [--repl|--explain|--dot output [distances] [route|route2]]",
+            PACKAGE_NAME.unwrap()
+        )
+        .as_str(),
+    );
+
+    println!("Reading input from {}", filename);
+    let input = fs::read_to_string(filename).expect("Failed to read file");
+
+    let mut repl = match Repl::new(&input) {
+        Ok(repl) => repl,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    if args.get(2).map(|a| a.as_str()) == Some("--dot") {
+        let output = args.get(3).expect("Missing output filename for --dot");
+        let options: Vec<&str> = args[4..].iter().map(|a| a.as_str()).collect();
+        let schedule = if options.contains(&"route") {
+            Some(Schedule::new(&repl.valves, 30, 1))
+        } else if options.contains(&"route2") {
+            Some(Schedule::new(&repl.valves, 26, 2))
+        } else {
+            None
+        };
+        let dot = to_dot(
+            &repl.valves,
+            options.contains(&"distances"),
+            schedule.as_ref(),
+        );
+        fs::write(output, dot).expect("Failed to write file");
+        println!("Wrote {}", output);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
+    println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
+
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
+    println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
+
+    if args.get(2).map(|a| a.as_str()) == Some("--explain") {
+        println!();
+        println!("{}", Schedule::new(&repl.valves, 30, 1));
+        println!();
+        println!("{}", Schedule::new(&repl.valves, 26, 2));
+    }
+}
+
+#[cfg(test)]
+mod tests_day16 {
+    use super::*;
+
+    const EXAMPLE1: &str = "
+Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+";
+
+    #[test]
+    fn test1_1() {
+        assert_eq!(solve_part1(EXAMPLE1), 1651);
+    }
+
+    #[test]
+    fn test1_network_1() {
+        let valves = parse_valves(EXAMPLE1).unwrap();
+        let network = Network::new(&valves, "AA");
+        assert_eq!(
+            network.names,
+            vec!["BB", "CC", "DD", "EE", "HH", "JJ", "AA"]
+        );
+        assert_eq!(network.start, 6);
+        assert_eq!(network.distances[6][4], 5);
+        assert_eq!(network.distances[4][5], 7);
+    }
+
+    #[test]
+    fn test1_valve_from_str1() {
+        assert_eq!(
+            Valve::from_str("Valve AA has flow rate=123; tunnels lead to valves DD, II, BB"),
+            Ok(Valve::new("AA", 123, vec!["DD", "II", "BB"]))
+        );
+    }
+
+    #[test]
+    fn test1_valve_from_str2() {
+        assert_eq!(
+            Valve::from_str("Valve HH has flow rate=22; tunnel leads to valve GG"),
+            Ok(Valve::new("HH", 22, vec!["GG"]))
+        );
+    }
+
+    #[test]
+    fn test1_valve_from_str3() {
+        assert!(Valve::from_str("Valve HH has flow rate=22; tunnel leads to valves GG").is_err());
+        assert!(Valve::from_str("Valve HH has flow rate=; tunnel leads to valve GG").is_err());
+        assert!(Valve::from_str("Valve HH has flow rate=1; tunnels lead to valves GG,").is_err());
+    }
+
+    #[test]
+    fn test1_parse_valves_1() {
+        assert_eq!(
+            parse_valves(
+                "
+Valve AA has flow rate=0; tunnels lead to valves BB, CC
+Valve BB has flow rate=13; tunnel leads to valve AA
+Valve CC has flow rate=2; tunnel leads to valve AA
+Valve DD has flow rate=2 tunnel leads to valve AA"
+            )
+            .err(),
+            Some("Line 5: Failed to parse valve from 'Valve DD has flow rate=2 tunnel leads to valve AA'".to_string())
+        );
+    }
+
+    #[test]
+    fn test1_parse_valves_2() {
+        assert_eq!(
+            parse_valves(
+                "Valve AA has flow rate=0; tunnels lead to valves BB, CC
+Valve BB has flow rate=13; tunnel leads to valve AA"
+            )
+            .err(),
+            Some("Line 1: Tunnel from AA leads to unknown valve CC".to_string())
+        );
+    }
+
+    #[test]
+    fn test1_parse_valves_3() {
+        assert_eq!(
+            parse_valves(
+                "Valve AA has flow rate=0; tunnel leads to valve BB
+Valve BB has flow rate=13; tunnels lead to valves AA, CC
+Valve CC has flow rate=2; tunnel leads to valve AA
+Valve AA has flow rate=2; tunnel leads to valve BB"
+            )
+            .err(),
+            Some("Line 4: Valve AA is already defined on line 1".to_string())
+        );
+        assert_eq!(
+            parse_valves(
+                "Valve AA has flow rate=0; tunnel leads to valve BB
+Valve BB has flow rate=13; tunnels lead to valves AA, CC
+Valve CC has flow rate=2; tunnel leads to valve AA"
+            )
+            .err(),
+            Some("Line 2: Tunnel from BB to CC has no tunnel back on line 3".to_string())
+        );
+    }
+
+    #[test]
+    fn test1_parse_valves_4() {
+        assert_eq!(
+            parse_valves(
+                "Valve BB has flow rate=13; tunnel leads to valve CC
+Valve CC has flow rate=2; tunnel leads to valve BB"
+            )
+            .err(),
+            Some("Start valve AA is not defined".to_string())
+        );
+        assert_eq!(
+            parse_valves(
+                "Valve AA has flow rate=0; tunnel leads to valve BB
+Valve BB has flow rate=13; tunnel leads to valve AA
+Valve CC has flow rate=2; tunnel leads to valve DD
+Valve DD has flow rate=0; tunnel leads to valve CC"
+            )
+            .err(),
+            Some("Line 3: Valve CC can not be reached from AA".to_string())
+        );
+    }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(
+            "Valve AA has flow rate=0; tunnels lead to valves BB, CC
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves AA, BB",
+        )
+        .unwrap();
+        assert_eq!(repl.execute("flowing", &[]), Ok("BB 13\nCC 2".to_string()));
+        assert_eq!(repl.execute("pressure", &["3"]), Ok("13".to_string()));
+        assert!(repl.execute("valve", &["DD"]).is_err());
+        assert_eq!(repl.part1(), Ok("416".to_string()));
+        assert_eq!(repl.part2(), Ok("360".to_string()));
+        assert!(repl.execute("pressure", &["30", "0"]).is_err());
+        assert!(repl.execute("explain", &["30", "0"]).is_err());
+    }
+
+    const EXAMPLE1_SCHEDULE_START: &str = "== Minute 1 ==
+No valves are open.
+You move to valve DD.
+
+== Minute 2 ==
+No valves are open.
+You open valve DD.
+
+== Minute 3 ==
+Valve DD is open, releasing 20 pressure.
+You move to valve CC.
+
+== Minute 4 ==
+Valve DD is open, releasing 20 pressure.
+You move to valve BB.
+
+== Minute 5 ==
+Valve DD is open, releasing 20 pressure.
+You open valve BB.
+
+== Minute 6 ==
+Valves BB and DD are open, releasing 33 pressure.
+You move to valve AA.
+";
+
+    #[test]
+    fn test1_schedule_1() {
+        let valves = parse_valves(EXAMPLE1).unwrap();
+        let schedule = Schedule::new(&valves, 30, 1);
+        assert_eq!(schedule.total(), 1651);
+        assert_eq!(schedule.minutes.len(), 30);
+        let text = schedule.to_string();
+        assert!(text.starts_with(EXAMPLE1_SCHEDULE_START));
+        assert!(text.contains(
+            "== Minute 25 ==
+Valves BB, CC, DD, EE, HH, and JJ are open, releasing 81 pressure.
+"
+        ));
+        assert!(text.ends_with("Released 1651 pressure in total."));
+    }
+
+    #[test]
+    fn test1_to_dot_1() {
+        let valves = parse_valves(
+            "Valve AA has flow rate=0; tunnels lead to valves BB, CC
+Valve BB has flow rate=13; tunnel leads to valve AA
+Valve CC has flow rate=2; tunnel leads to valve AA",
+        )
+        .unwrap();
+        assert_eq!(
+            to_dot(&valves, false, None),
+            r#"graph valves {
+  node [shape=circle, style=filled, fontname=monospace];
+  AA [label="AA\n0", width=0.50, fillcolor=lightgrey];
+  BB [label="BB\n13", width=1.50, fillcolor=gold];
+  CC [label="CC\n2", width=0.65, fillcolor=gold];
+  AA -- BB;
+  AA -- CC;
+}"#
+        );
+    }
+
+    #[test]
+    fn test1_to_dot_2() {
+        let valves = parse_valves(EXAMPLE1).unwrap();
+        let schedule = Schedule::new(&valves, 30, 1);
+        let dot = to_dot(&valves, true, Some(&schedule));
+        assert!(dot.contains("  AA -- DD [color=\"red\", penwidth=3];"));
+        assert!(dot.contains(
+            "  JJ [label=\"JJ\\n21\", width=1.45, fillcolor=gold, color=red, penwidth=3];"
+        ));
+        assert!(dot.contains("  HH -- JJ [label=\"7\", style=dashed"));
+        assert!(dot.contains("  BB -- CC [color=\"red\", penwidth=3];"));
+    }
+
+    #[test]
+    fn test2_1() {
+        assert_eq!(solve_part2(EXAMPLE1), 1707);
+    }
+
+    #[test]
+    fn test2_schedule_1() {
+        let valves = parse_valves(EXAMPLE1).unwrap();
+        let schedule = Schedule::new(&valves, 26, 2);
+        assert_eq!(schedule.total(), 1707);
+        assert_eq!(
+            schedule.minutes.iter().map(|m| m.released).sum::<usize>(),
+            1707
+        );
+        assert_eq!(schedule.minutes[25].open.len(), 6);
+        let text = schedule.to_string();
+        assert!(text.contains("The elephant opens valve"));
+    }
+
+    #[test]
+    fn test2_max_pressure_1() {
+        let valves = parse_valves(EXAMPLE1).unwrap();
+        let network = Network::new(&valves, "AA");
+        assert_eq!(max_pressure(&network, 30, 1), 1651);
+        assert_eq!(max_pressure(&network, 26, 1), 1327);
+        assert_eq!(max_pressure(&network, 26, 2), 1707);
+        assert_eq!(max_pressure(&network, 26, 3), 1794);
+        assert_eq!(max_pressure(&network, 0, 2), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "At least one agent")]
+    fn test2_max_pressure_2() {
+        let valves = parse_valves(EXAMPLE1).unwrap();
+        let network = Network::new(&valves, "AA");
+        max_pressure(&network, 26, 0);
+    }
+}
@@ -0,0 +1,1568 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::process;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Push {
+    Left,
+    Right,
+}
+
+impl Push {
+    fn from_str(s: &str) -> Vec<Push> {
+        s.trim_end()
+            .chars()
+            .map(|c| {
+                if c == '<' {
+                    Push::Left
+                } else if c == '>' {
+                    Push::Right
+                } else {
+                    panic!("Unknown push {}", c);
+                }
+            })
+            .collect()
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Push::Left => '<',
+            Push::Right => '>',
+        }
+    }
+
+    fn inverse(&self) -> Push {
+        match self {
+            Push::Left => Push::Right,
+            Push::Right => Push::Left,
+        }
+    }
+}
+
+const PUZZLE_WIDTH: usize = 7;
+// Rows of the tower are u64 bitmasks
+const MAX_WIDTH: usize = 64;
+
+// The rocks of the puzzle in the order they fall, separated by empty lines
+const PUZZLE_ROCKS: &str = "\
+####
+
+.#.
+###
+.#.
+
+..#
+..#
+###
+
+#
+#
+#
+#
+
+##
+##";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Shape {
+    // Bottom row first, with the leftmost column in the lowest bit like the tower rows
+    rows: Vec<u64>,
+    width: usize,
+}
+
+// Columns of a line of ascii art that are part of the rock
+fn columns(line: &str) -> impl Iterator<Item = usize> + '_ {
+    line.char_indices()
+        .filter(|(_, c)| *c == '#')
+        .map(|(x, _)| x)
+}
+
+impl Shape {
+    fn from_str(s: &str) -> Result<Shape, String> {
+        let lines: Vec<&str> = s.lines().map(|l| l.trim_end()).collect();
+        if let Some(c) = lines
+            .iter()
+            .flat_map(|l| l.chars())
+            .find(|c| *c != '#' && *c != '.')
+        {
+            return Err(format!("Unknown character '{}' in rock", c));
+        }
+        let left = lines.iter().flat_map(|l| columns(l)).min();
+        let right = lines.iter().flat_map(|l| columns(l)).max();
+        let (left, right) = match (left, right) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return Err("Rock has no #".to_string()),
+        };
+        let width = right - left + 1;
+        if width > MAX_WIDTH {
+            return Err(format!("Rock is {} wide, at most {}", width, MAX_WIDTH));
+        }
+        let rows = lines
+            .iter()
+            .rev()
+            .skip_while(|l| !l.contains('#'))
+            .map(|l| columns(l).fold(0u64, |row, x| row | 1 << (x - left)))
+            .collect::<Vec<u64>>();
+        let height = rows.iter().rposition(|r| *r != 0).unwrap() + 1;
+        Ok(Shape {
+            rows: rows[..height].to_vec(),
+            width,
+        })
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows.iter().rev().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width {
+                write!(f, "{}", if row & (1 << x) != 0 { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// How wide the chamber is, the shapes that can fall, and the order they fall in
+#[derive(Debug, Clone)]
+struct Chamber {
+    width: usize,
+    shapes: Vec<Shape>,
+    sequence: Vec<usize>,
+}
+
+impl Chamber {
+    fn new(rocks: &str) -> Result<Chamber, String> {
+        let shapes = rocks
+            .split("\n\n")
+            .filter(|s| !s.trim().is_empty())
+            .enumerate()
+            .map(|(i, s)| Shape::from_str(s).map_err(|e| format!("Rock {}: {}", i, e)))
+            .collect::<Result<Vec<Shape>, String>>()?;
+        if shapes.is_empty() {
+            return Err("No rocks".to_string());
+        }
+        let sequence = (0..shapes.len()).collect();
+        Chamber {
+            width: PUZZLE_WIDTH,
+            shapes,
+            sequence,
+        }
+        .with_width(PUZZLE_WIDTH)
+    }
+
+    fn with_width(mut self, width: usize) -> Result<Chamber, String> {
+        if !(1..=MAX_WIDTH).contains(&width) {
+            return Err(format!("Chamber width must be 1..={}", MAX_WIDTH));
+        }
+        if let Some(i) = self.shapes.iter().position(|s| s.width > width) {
+            return Err(format!(
+                "Rock {} is {} wide, the chamber only {}",
+                i, self.shapes[i].width, width
+            ));
+        }
+        self.width = width;
+        Ok(self)
+    }
+
+    // Rocks appear two units from the left wall, or as far left as they fit
+    fn spawn_x(&self, shape: &Shape) -> usize {
+        2.min(self.width - shape.width)
+    }
+
+    fn puzzle() -> Chamber {
+        Chamber::new(PUZZLE_ROCKS).unwrap()
+    }
+
+    // Rocks fall in the order of the given indices, e.g. 0,0,3
+    fn with_sequence(mut self, sequence: &str) -> Result<Chamber, String> {
+        let sequence = sequence
+            .split(',')
+            .map(|i| match i.trim().parse::<usize>() {
+                Ok(i) if i < self.shapes.len() => Ok(i),
+                _ => Err(format!(
+                    "Rock '{}' in sequence is not one of 0..{}",
+                    i,
+                    self.shapes.len()
+                )),
+            })
+            .collect::<Result<Vec<usize>, String>>()?;
+        self.sequence = sequence;
+        Ok(self)
+    }
+
+    fn rock_count(&self) -> usize {
+        self.sequence.len()
+    }
+
+    // Shape of the r:th rock to fall
+    fn shape(&self, r: usize) -> &Shape {
+        &self.shapes[self.shape_index(r)]
+    }
+
+    fn shape_index(&self, r: usize) -> usize {
+        self.sequence[r % self.sequence.len()]
+    }
+}
+
+struct Rock<'a> {
+    x: usize,
+    y: usize,
+    shape: &'a Shape,
+}
+
+impl<'a> fmt::Debug for Rock<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)?;
+        for row in self.shape.rows.iter().rev() {
+            write!(f, "\n  {:b}", row << self.x)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Rock<'a> {
+    fn new(x: usize, y: usize, shape: &'a Shape) -> Rock<'a> {
+        Rock { x, y, shape }
+    }
+
+    fn row(&self, i: usize) -> u64 {
+        self.shape.rows[i]
+    }
+
+    fn shifted_row(&self, i: usize) -> u64 {
+        self.row(i) << self.x
+    }
+
+    // Moves the rock unless it would hit a wall of a chamber that wide
+    fn apply_push(&mut self, p: &Push, width: usize) {
+        match p {
+            Push::Left => {
+                if self.x > 0 {
+                    self.x -= 1;
+                }
+            }
+            Push::Right => {
+                if self.x + self.shape.width < width {
+                    self.x += 1;
+                }
+            }
+        }
+    }
+
+    fn overlaps_tower(&self, tower: &Tower) -> bool {
+        assert!(self.y > 0);
+        for r in 0..self.row_count() {
+            let y = r + self.y;
+            if y >= tower.row_count() {
+                continue;
+            }
+            if (tower.row(y) & self.shifted_row(r)) != 0 {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn row_count(&self) -> usize {
+        self.shape.height()
+    }
+}
+
+// Rows are only pruned once there are this many, and then again when their number has doubled
+const PRUNE_MIN_ROWS: usize = 128;
+
+struct Tower {
+    width: usize,
+    rows: Vec<u64>,
+    // Rows below the first one in rows, that have been pruned or skipped by a cycle
+    y_offset: usize,
+    prune_at: Option<usize>,
+}
+
+impl Tower {
+    // Starts with the floor as the only row
+    fn new(width: usize) -> Tower {
+        Tower {
+            width,
+            rows: vec![u64::MAX >> (MAX_WIDTH - width)],
+            y_offset: 0,
+            prune_at: None,
+        }
+    }
+
+    // Keeps memory bounded by pruning the rows no rock can reach as the tower grows
+    fn with_pruning(mut self) -> Tower {
+        self.prune_at = Some(PRUNE_MIN_ROWS);
+        self
+    }
+
+    // Drops the rows below the lowest one a rock could reach, except for the row under it
+    // that rocks land on
+    fn prune(&mut self) {
+        let depth = self.surface(usize::MAX).unwrap().len();
+        let keep_from = self.row_count() - depth - 1;
+        self.rows.drain(..keep_from - self.y_offset);
+        self.y_offset = keep_from;
+    }
+
+    fn row_count(&self) -> usize {
+        self.rows.len() + self.y_offset
+    }
+
+    fn height(&self) -> usize {
+        self.row_count() - 1
+    }
+
+    fn row(&self, y: usize) -> u64 {
+        assert!(y >= self.y_offset);
+        assert!(y <= self.rows.len() + self.y_offset);
+        self.rows[y - self.y_offset]
+    }
+
+    fn add_rock(&mut self, rock: &Rock) {
+        assert!(rock.y > 0);
+        assert!(rock.y >= self.y_offset);
+        assert!((rock.y - self.y_offset) <= self.rows.len() + 1);
+        for r in 0..rock.row_count() {
+            let rock_row = rock.shifted_row(r);
+            let y = (r + rock.y) - self.y_offset;
+            if y < self.rows.len() {
+                self.rows[y] |= rock_row;
+            } else {
+                self.rows.push(rock_row);
+            }
+        }
+        if let Some(prune_at) = self.prune_at {
+            if self.rows.len() >= prune_at {
+                self.prune();
+                self.prune_at = Some(PRUNE_MIN_ROWS.max(2 * self.rows.len()));
+            }
+        }
+    }
+}
+
+impl Tower {
+    // Air that a falling rock could reach, from the top row down to the lowest reachable row.
+    // Rocks only move sideways and down, so nothing below or behind it matters any more.
+    // None when it reaches deeper than max_depth rows, like a chimney that never gets filled.
+    fn surface(&self, max_depth: usize) -> Option<Vec<u64>> {
+        let full = u64::MAX >> (MAX_WIDTH - self.width);
+        let mut surface: Vec<u64> = Vec::new();
+        let mut above = full;
+        for y in (self.y_offset..self.row_count()).rev() {
+            let air = !self.row(y) & full;
+            let mut reached = air & above;
+            loop {
+                let spread = (reached | reached << 1 | reached >> 1) & air;
+                if spread == reached {
+                    break;
+                }
+                reached = spread;
+            }
+            if reached == 0 {
+                break;
+            }
+            if surface.len() == max_depth {
+                return None;
+            }
+            surface.push(reached);
+            above = reached;
+        }
+        Some(surface)
+    }
+}
+
+impl fmt::Display for Tower {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Picture::from_tower(self, None))
+    }
+}
+
+// Deeper surfaces are not used for finding cycles, they are rare and slow to compare
+const MAX_SURFACE_DEPTH: usize = 512;
+
+// Everything that decides where the following rocks come to rest
+#[derive(Hash, PartialEq, Eq, Debug)]
+struct State {
+    rock_index: usize,
+    push_index: usize,
+    surface: Vec<u64>,
+}
+
+// Rocks dropped before the tower starts repeating, and the rocks and rows of each repetition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cycle {
+    prefix: usize,
+    period: usize,
+    height: usize,
+}
+
+impl Cycle {
+    // Height after rock_count rocks, from the heights of the first prefix + period rocks
+    fn extrapolate(&self, heights: &[usize], rock_count: usize) -> usize {
+        assert!(rock_count >= self.prefix);
+        let repetitions = (rock_count - self.prefix) / self.period;
+        let rest = (rock_count - self.prefix) % self.period;
+        heights[self.prefix + rest] + repetitions * self.height
+    }
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "after {} rocks, every {} rocks add {} rows",
+            self.prefix, self.period, self.height
+        )
+    }
+}
+
+fn drop_rock<'a>(
+    chamber: &'a Chamber,
+    r: usize,
+    tower: &mut Tower,
+    pushes: &[Push],
+    start_push_index: usize,
+) -> (Rock<'a>, usize) {
+    drop_rock_traced(chamber, r, tower, pushes, start_push_index, None)
+}
+
+fn drop_rock_traced<'a>(
+    chamber: &'a Chamber,
+    r: usize,
+    tower: &mut Tower,
+    pushes: &[Push],
+    start_push_index: usize,
+    mut trace: Option<&mut Vec<Event>>,
+) -> (Rock<'a>, usize) {
+    let shape = chamber.shape(r);
+    let mut rock = Rock::new(chamber.spawn_x(shape), tower.row_count() + 3, shape);
+    let mut emit = |event: Event| {
+        if let Some(trace) = trace.as_mut() {
+            trace.push(event);
+        }
+    };
+    emit(Event::Spawn {
+        rock: r,
+        shape: chamber.shape_index(r),
+        x: rock.x,
+        y: rock.y,
+    });
+
+    let mut push_index = start_push_index;
+    loop {
+        let push = &pushes[push_index];
+        let x = rock.x;
+        rock.apply_push(push, chamber.width);
+        if rock.overlaps_tower(tower) {
+            rock.apply_push(&push.inverse(), chamber.width);
+        }
+        emit(Event::Push {
+            rock: r,
+            push: push_index,
+            direction: *push,
+            moved: rock.x != x,
+        });
+        push_index = (push_index + 1) % pushes.len();
+        rock.y -= 1;
+        if rock.y == 0 || rock.overlaps_tower(tower) {
+            rock.y += 1;
+            tower.add_rock(&rock);
+            emit(Event::Rest {
+                rock: r,
+                x: rock.x,
+                y: rock.y,
+            });
+            break;
+        }
+        emit(Event::Fall { rock: r, y: rock.y });
+    }
+
+    (rock, push_index)
+}
+
+fn get_tower_height_from(
+    chamber: &Chamber,
+    mut tower: Tower,
+    pushes: &[Push],
+    rock_count: usize,
+) -> usize {
+    let mut push_index = 0;
+    for r in 0..rock_count {
+        let (_, new_push_index) = drop_rock(chamber, r, &mut tower, pushes, push_index);
+        push_index = new_push_index;
+    }
+
+    tower.height()
+}
+
+// Rocks to look for a cycle in, towers without one are simulated rock by rock instead
+const MAX_CYCLE_ROCKS: usize = 100000;
+
+// Drops rocks until the state after a rock has been seen before, or max_rocks have fallen.
+// Returns the height after each number of rocks, starting with 0 for no rocks.
+fn find_cycle(chamber: &Chamber, pushes: &[Push], max_rocks: usize) -> (Vec<usize>, Option<Cycle>) {
+    let mut tower = Tower::new(chamber.width).with_pruning();
+    let mut push_index = 0;
+    let mut heights: Vec<usize> = vec![0];
+    let mut seen: HashMap<State, usize> = HashMap::new();
+    for r in 0..max_rocks {
+        let (_, new_push_index) = drop_rock(chamber, r, &mut tower, pushes, push_index);
+        push_index = new_push_index;
+        heights.push(tower.height());
+
+        let dropped = r + 1;
+        let surface = match tower.surface(MAX_SURFACE_DEPTH) {
+            Some(surface) => surface,
+            None => continue,
+        };
+        let state = State {
+            rock_index: dropped % chamber.rock_count(),
+            push_index,
+            surface,
+        };
+        if let Some(prefix) = seen.insert(state, dropped) {
+            let cycle = Cycle {
+                prefix,
+                period: dropped - prefix,
+                height: heights[dropped] - heights[prefix],
+            };
+            return (heights, Some(cycle));
+        }
+    }
+    (heights, None)
+}
+
+fn get_tower_height(
+    chamber: &Chamber,
+    pushes: &[Push],
+    rock_count: usize,
+) -> Result<usize, String> {
+    get_tower_height_within(chamber, pushes, rock_count, MAX_CYCLE_ROCKS)
+}
+
+// Looks for a cycle among the first max_cycle_rocks rocks only, so that memory and time stay
+// bounded. More rocks than that can only be counted when the tower repeats.
+fn get_tower_height_within(
+    chamber: &Chamber,
+    pushes: &[Push],
+    rock_count: usize,
+    max_cycle_rocks: usize,
+) -> Result<usize, String> {
+    match find_cycle(chamber, pushes, rock_count.min(max_cycle_rocks)) {
+        (heights, Some(cycle)) => Ok(cycle.extrapolate(&heights, rock_count)),
+        (heights, None) if rock_count < heights.len() => Ok(heights[rock_count]),
+        (_, None) => Err(format!("No cycle within {} rocks", max_cycle_rocks)),
+    }
+}
+
+// What happened to a rock, written one per line to trace files as in s 0 0 2 4
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Event {
+    Spawn {
+        rock: usize,
+        shape: usize,
+        x: usize,
+        y: usize,
+    },
+    Push {
+        rock: usize,
+        push: usize,
+        direction: Push,
+        moved: bool,
+    },
+    Fall {
+        rock: usize,
+        y: usize,
+    },
+    Rest {
+        rock: usize,
+        x: usize,
+        y: usize,
+    },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Spawn { rock, shape, x, y } => write!(f, "s {} {} {} {}", rock, shape, x, y),
+            Event::Push {
+                rock,
+                push,
+                direction,
+                moved,
+            } => write!(
+                f,
+                "p {} {} {} {}",
+                rock,
+                push,
+                direction.symbol(),
+                if *moved { '+' } else { '-' }
+            ),
+            Event::Fall { rock, y } => write!(f, "f {} {}", rock, y),
+            Event::Rest { rock, x, y } => write!(f, "r {} {} {}", rock, x, y),
+        }
+    }
+}
+
+impl FromStr for Event {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Event, String> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let number = |i: usize| {
+            words
+                .get(i)
+                .and_then(|w| w.parse::<usize>().ok())
+                .ok_or(format!("Failed to parse event from '{}'", s))
+        };
+        let event = match (words.first(), words.len()) {
+            (Some(&"s"), 5) => Event::Spawn {
+                rock: number(1)?,
+                shape: number(2)?,
+                x: number(3)?,
+                y: number(4)?,
+            },
+            (Some(&"p"), 5) => Event::Push {
+                rock: number(1)?,
+                push: number(2)?,
+                direction: match words[3] {
+                    "<" => Push::Left,
+                    ">" => Push::Right,
+                    _ => return Err(format!("Unknown push in '{}'", s)),
+                },
+                moved: match words[4] {
+                    "+" => true,
+                    "-" => false,
+                    _ => return Err(format!("Expected + or - in '{}'", s)),
+                },
+            },
+            (Some(&"f"), 3) => Event::Fall {
+                rock: number(1)?,
+                y: number(2)?,
+            },
+            (Some(&"r"), 4) => Event::Rest {
+                rock: number(1)?,
+                x: number(2)?,
+                y: number(3)?,
+            },
+            _ => return Err(format!("Failed to parse event from '{}'", s)),
+        };
+        Ok(event)
+    }
+}
+
+fn trace(chamber: &Chamber, pushes: &[Push], rock_count: usize) -> Vec<Event> {
+    let mut tower = Tower::new(chamber.width);
+    let mut events: Vec<Event> = Vec::new();
+    let mut push_index = 0;
+    for r in 0..rock_count {
+        let (_, new_push_index) = drop_rock_traced(
+            chamber,
+            r,
+            &mut tower,
+            pushes,
+            push_index,
+            Some(&mut events),
+        );
+        push_index = new_push_index;
+    }
+    events
+}
+
+fn write_trace(chamber: &Chamber, events: &[Event]) -> String {
+    let mut lines = vec![format!(
+        "# day17 trace, width {}, {} events",
+        chamber.width,
+        events.len()
+    )];
+    lines.extend(events.iter().map(|e| e.to_string()));
+    lines.join("\n") + "\n"
+}
+
+fn parse_trace(s: &str) -> Result<Vec<Event>, String> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty() && !l.starts_with('#'))
+        .map(|(i, l)| Event::from_str(l).map_err(|e| format!("Line {}: {}", i + 1, e)))
+        .collect()
+}
+
+// The tower after some events, and the rock that is still falling if any
+struct Replay<'a> {
+    tower: Tower,
+    falling: Option<Rock<'a>>,
+}
+
+// Rebuilds the tower from events, checking that each one follows from the ones before
+fn replay<'a>(chamber: &'a Chamber, events: &[Event]) -> Result<Replay<'a>, String> {
+    let mut tower = Tower::new(chamber.width);
+    let mut falling: Option<(usize, Rock)> = None;
+    for (i, event) in events.iter().enumerate() {
+        let error = |message: &str| Err(format!("Event {} '{}': {}", i, event, message));
+        match (*event, falling.as_mut()) {
+            (Event::Spawn { rock, shape, x, y }, None) => match chamber.shapes.get(shape) {
+                Some(shape) => falling = Some((rock, Rock::new(x, y, shape))),
+                None => return error("Unknown shape"),
+            },
+            (
+                Event::Push { rock, .. } | Event::Fall { rock, .. } | Event::Rest { rock, .. },
+                None,
+            ) => return error(&format!("Rock {} has not been spawned", rock)),
+            (Event::Spawn { .. }, Some((r, _))) => {
+                return error(&format!("Rock {} has not come to rest", r))
+            }
+            (
+                Event::Push { rock, .. } | Event::Fall { rock, .. } | Event::Rest { rock, .. },
+                Some((r, _)),
+            ) if rock != *r => return error(&format!("Rock {} is falling", r)),
+            (
+                Event::Push {
+                    direction, moved, ..
+                },
+                Some((_, rock)),
+            ) => {
+                let x = rock.x;
+                rock.apply_push(&direction, chamber.width);
+                if rock.overlaps_tower(&tower) {
+                    rock.apply_push(&direction.inverse(), chamber.width);
+                }
+                if moved != (rock.x != x) {
+                    return error("Push does not match the tower");
+                }
+            }
+            (Event::Fall { y, .. }, Some((_, rock))) => {
+                if y + 1 != rock.y || y == 0 {
+                    return error("Fall does not match the tower");
+                }
+                rock.y = y;
+                if rock.overlaps_tower(&tower) {
+                    return error("Fall does not match the tower");
+                }
+            }
+            (Event::Rest { x, y, .. }, Some((_, rock))) => {
+                if (x, y) != (rock.x, rock.y) {
+                    return error(&format!("Rock is at ({}, {})", rock.x, rock.y));
+                }
+                tower.add_rock(rock);
+                falling = None;
+            }
+        }
+    }
+    Ok(Replay {
+        tower,
+        falling: falling.map(|(_, rock)| rock),
+    })
+}
+
+// Index of the first event that differs between two traces
+fn first_difference(a: &[Event], b: &[Event]) -> Option<usize> {
+    match a.iter().zip(b.iter()).position(|(a, b)| a != b) {
+        Some(i) => Some(i),
+        None if a.len() != b.len() => Some(a.len().min(b.len())),
+        None => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Air,
+    // Rock at rest, with the index of its shape when known
+    Rock(Option<usize>),
+    Falling,
+}
+
+// Colours of the shapes in images, repeating when there are more shapes
+const PALETTE: [(u8, u8, u8); 5] = [
+    (230, 80, 60),
+    (240, 180, 40),
+    (80, 180, 90),
+    (60, 140, 220),
+    (170, 90, 200),
+];
+
+// Cells of a tower to draw, rows from the bottom up
+struct Picture {
+    width: usize,
+    // Row of rows[0], the floor is below row 1
+    bottom: usize,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Picture {
+    fn new(width: usize) -> Picture {
+        Picture {
+            width,
+            bottom: 1,
+            rows: Vec::new(),
+        }
+    }
+
+    // Rows the tower has kept, with rocks of unknown shapes, and the falling rock if any
+    fn from_tower(tower: &Tower, falling: Option<&Rock>) -> Picture {
+        let mut picture = Picture::new(tower.width);
+        picture.bottom = tower.y_offset.max(1);
+        for y in picture.bottom..tower.row_count() {
+            let row = tower.row(y);
+            let cells = (0..tower.width)
+                .map(|x| match row & (1 << x) {
+                    0 => Cell::Air,
+                    _ => Cell::Rock(None),
+                })
+                .collect();
+            picture.rows.push(cells);
+        }
+        if let Some(rock) = falling {
+            picture.paint(rock, Cell::Falling);
+        }
+        picture
+    }
+
+    // Rocks coloured by shape as they come to rest in the trace
+    fn from_trace(chamber: &Chamber, events: &[Event]) -> Result<Picture, String> {
+        let replay = replay(chamber, events)?;
+        let mut picture = Picture::new(chamber.width);
+        let mut kind = 0;
+        for event in events {
+            match *event {
+                Event::Spawn { shape, .. } => kind = shape,
+                Event::Rest { x, y, .. } => {
+                    let rock = Rock::new(x, y, &chamber.shapes[kind]);
+                    picture.paint(&rock, Cell::Rock(Some(kind)));
+                }
+                _ => {}
+            }
+        }
+        if let Some(rock) = replay.falling {
+            picture.paint(&rock, Cell::Falling);
+        }
+        Ok(picture)
+    }
+
+    fn paint(&mut self, rock: &Rock, cell: Cell) {
+        assert!(rock.y >= self.bottom);
+        for r in 0..rock.row_count() {
+            let y = rock.y + r - self.bottom;
+            while self.rows.len() <= y {
+                self.rows.push(vec![Cell::Air; self.width]);
+            }
+            let row = rock.shifted_row(r);
+            for x in (0..self.width).filter(|x| row & (1 << x) != 0) {
+                self.rows[y][x] = cell;
+            }
+        }
+    }
+
+    // Drawn as in the puzzle, with only the top rows when given a window
+    fn draw(&self, window: Option<usize>) -> String {
+        let shown = window.map_or(self.rows.len(), |w| w.min(self.rows.len()));
+        let mut lines: Vec<String> = self
+            .rows
+            .iter()
+            .rev()
+            .take(shown)
+            .map(|row| {
+                let cells: String = row
+                    .iter()
+                    .map(|cell| match cell {
+                        Cell::Air => '.',
+                        Cell::Rock(_) => '#',
+                        Cell::Falling => '@',
+                    })
+                    .collect();
+                format!("|{}|", cells)
+            })
+            .collect();
+        if shown == self.rows.len() && self.bottom == 1 {
+            lines.push(format!("+{}+", "-".repeat(self.width)));
+        } else {
+            lines.push(format!("|{}|", "~".repeat(self.width)));
+        }
+        lines.join("\n")
+    }
+
+    // Binary PPM with scale x scale pixels per cell. Tall towers are cut into strips of
+    // strip_rows rows, placed left to right with the bottom of the tower in the first one.
+    fn ppm(&self, scale: usize, strip_rows: usize) -> Vec<u8> {
+        let strips = self.rows.len().div_ceil(strip_rows).max(1);
+        let cells_high = self.rows.len().clamp(1, strip_rows);
+        let cells_wide = strips * (self.width + 1) - 1;
+        let (width, height) = (cells_wide * scale, cells_high * scale);
+        let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        for py in 0..height {
+            let row_in_strip = cells_high - 1 - py / scale;
+            for px in 0..width {
+                let (strip, x) = (px / scale / (self.width + 1), px / scale % (self.width + 1));
+                let y = strip * strip_rows + row_in_strip;
+                let (r, g, b) = match self.rows.get(y).and_then(|row| row.get(x)) {
+                    _ if x == self.width => (0, 0, 0),
+                    Some(Cell::Rock(Some(kind))) => PALETTE[kind % PALETTE.len()],
+                    Some(Cell::Rock(None)) => (160, 160, 160),
+                    Some(Cell::Falling) => (255, 255, 255),
+                    Some(Cell::Air) | None => (24, 24, 32),
+                };
+                image.extend([r, g, b]);
+            }
+        }
+        image
+    }
+}
+
+impl fmt::Display for Picture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.draw(None))
+    }
+}
+
+// The tower after rock_count rocks, with every rock coloured by its shape
+fn picture(chamber: &Chamber, pushes: &[Push], rock_count: usize) -> Picture {
+    let mut tower = Tower::new(chamber.width).with_pruning();
+    let mut picture = Picture::new(chamber.width);
+    let mut push_index = 0;
+    for r in 0..rock_count {
+        let (rock, new_push_index) = drop_rock(chamber, r, &mut tower, pushes, push_index);
+        picture.paint(&rock, Cell::Rock(Some(chamber.shape_index(r))));
+        push_index = new_push_index;
+    }
+    picture
+}
+
+fn answer(result: &Result<usize, String>) -> String {
+    match result {
+        Ok(height) => height.to_string(),
+        Err(e) => format!("unknown, {}", e),
+    }
+}
+
+fn solve_part1(input: &str, chamber: &Chamber) -> Result<usize, String> {
+    let pushes = Push::from_str(input);
+
+    get_tower_height(chamber, &pushes[..], 2022)
+}
+
+fn solve_part2(input: &str, chamber: &Chamber) -> Result<usize, String> {
+    let pushes = Push::from_str(input);
+
+    get_tower_height(chamber, &pushes[..], 1000000000000)
+}
+
+// The puzzle's rocks, or those given with --rocks file, falling in --sequence order into a
+// chamber that is 7 or --width wide
+fn chamber_from_args(args: &[String]) -> Result<Chamber, String> {
+    let option = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .map(|i| args.get(i + 1).ok_or(format!("Missing value for {}", name)))
+            .transpose()
+    };
+    let chamber = match option("--rocks")? {
+        Some(filename) => {
+            let rocks = fs::read_to_string(filename)
+                .map_err(|e| format!("Failed to read {}: {}", filename, e))?;
+            Chamber::new(&rocks)?
+        }
+        None => Chamber::puzzle(),
+    };
+    let chamber = match option("--sequence")? {
+        Some(sequence) => chamber.with_sequence(sequence)?,
+        None => chamber,
+    };
+    match option("--width")? {
+        Some(width) => match width.parse::<usize>() {
+            Ok(width) => chamber.with_width(width),
+            Err(_) => Err(format!("Failed to parse width from '{}'", width)),
+        },
+        None => Ok(chamber),
+    }
+}
+
+struct Repl<'a> {
+    chamber: &'a Chamber,
+    pushes: Vec<Push>,
+}
+
+impl<'a> aoc_repl::Introspect for Repl<'a> {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![
+            (
+                "height",
+                "<rocks>",
+                "Height of the tower after that many rocks",
+            ),
+            (
+                "draw",
+                "<rocks> [rows]",
+                "Draw the top rows of the tower after that many rocks",
+            ),
+            ("cycle", "", "Find where the tower starts repeating"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "height" => {
+                let rock_count: usize = aoc_repl::arg(args, 0, "rocks")?;
+                get_tower_height(self.chamber, &self.pushes, rock_count).map(|h| h.to_string())
+            }
+            "draw" => {
+                let rock_count: usize = aoc_repl::arg(args, 0, "rocks")?;
+                let rows: usize = aoc_repl::arg_or(args, 1, "rows", 20)?;
+                Ok(picture(self.chamber, &self.pushes, rock_count).draw(Some(rows)))
+            }
+            "cycle" => match find_cycle(self.chamber, &self.pushes, MAX_CYCLE_ROCKS) {
+                (_, Some(cycle)) => Ok(format!("Cycle {}", cycle)),
+                (_, None) => Ok(format!("No cycle within {} rocks", MAX_CYCLE_ROCKS)),
+            },
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        get_tower_height(self.chamber, &self.pushes, 2022).map(|h| h.to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        get_tower_height(self.chamber, &self.pushes, 1000000000000).map(|h| h.to_string())
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
+    let filename = args.get(1).expect(
+        format!(
+            "Usage: {} input-filename [--repl] [--rocks file] [--sequence 0,1,...] [--width n] [--cycle] [--check rocks] \
+            [--trace output [rocks]|--replay trace [events [rows]]|--diff trace trace] \
+            [--draw rocks [rows]|--image output [rocks]]",
+            PACKAGE_NAME.unwrap()
+        )
+        .as_str(),
+    );
+
+    println!("Reading input from {}", filename);
+    let input = fs::read_to_string(filename).expect("Failed to read file");
+
+    let chamber = match chamber_from_args(&args) {
+        Ok(chamber) => chamber,
+        Err(e) => {
+            println!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let pushes = Push::from_str(&input);
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl {
+            chamber: &chamber,
+            pushes,
+        };
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+    let read_trace = |filename: &String| {
+        let text = fs::read_to_string(filename).expect("Failed to read trace");
+        parse_trace(&text).unwrap_or_else(|e| {
+            println!("Error: {}: {}", filename, e);
+            process::exit(1);
+        })
+    };
+    match args.get(2).map(|a| a.as_str()) {
+        Some("--trace") => {
+            let output = args.get(3).expect("Missing output filename for --trace");
+            let rock_count = args
+                .get(4)
+                .map_or(2022, |n| n.parse().expect("Not a number"));
+            let events = trace(&chamber, &pushes, rock_count);
+            fs::write(output, write_trace(&chamber, &events)).expect("Failed to write file");
+            println!("Wrote {} events to {}", events.len(), output);
+            return;
+        }
+        Some("--replay") => {
+            let events = read_trace(args.get(3).expect("Missing trace for --replay"));
+            let count = args
+                .get(4)
+                .map_or(events.len(), |n| n.parse().expect("Not a number"));
+            let window = args.get(5).map(|n| n.parse().expect("Not a number"));
+            match Picture::from_trace(&chamber, &events[..count.min(events.len())]) {
+                Ok(picture) => println!("{}", picture.draw(window)),
+                Err(e) => println!("Error: {}", e),
+            }
+            return;
+        }
+        Some("--draw") => {
+            let rock_count = args
+                .get(3)
+                .map_or(2022, |n| n.parse().expect("Not a number"));
+            let window = args.get(4).map(|n| n.parse().expect("Not a number"));
+            println!("{}", picture(&chamber, &pushes, rock_count).draw(window));
+            return;
+        }
+        Some("--image") => {
+            let output = args.get(3).expect("Missing output filename for --image");
+            let rock_count = args
+                .get(4)
+                .map_or(2022, |n| n.parse().expect("Not a number"));
+            let image = picture(&chamber, &pushes, rock_count).ppm(2, 1000);
+            fs::write(output, image).expect("Failed to write file");
+            println!("Wrote {}", output);
+            return;
+        }
+        Some("--diff") => {
+            let a = read_trace(args.get(3).expect("Missing first trace for --diff"));
+            let b = read_trace(args.get(4).expect("Missing second trace for --diff"));
+            match first_difference(&a, &b) {
+                Some(i) => println!(
+                    "Event {} differs: {} vs {}",
+                    i,
+                    a.get(i).map_or("nothing".to_string(), |e| e.to_string()),
+                    b.get(i).map_or("nothing".to_string(), |e| e.to_string())
+                ),
+                None => println!("Traces are identical"),
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input, &chamber));
+    println!("Answer 1: {}", answer(&answer1));
+    println!("Stats 1: {}", stats1);
+
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input, &chamber));
+    println!("Answer 2: {}", answer(&answer2));
+    println!("Stats 2: {}", stats2);
+
+    if args.iter().any(|a| a == "--cycle") {
+        match find_cycle(&chamber, &pushes, MAX_CYCLE_ROCKS) {
+            (_, Some(cycle)) => println!("Cycle {}", cycle),
+            (_, None) => println!("No cycle within {} rocks", MAX_CYCLE_ROCKS),
+        }
+    }
+
+    // Simulates every rock, in bounded memory, to compare with the height found from the cycle
+    if let Some(i) = args.iter().position(|a| a == "--check") {
+        let rock_count = args
+            .get(i + 1)
+            .and_then(|n| n.parse::<usize>().ok())
+            .expect("Missing number of rocks for --check");
+        let tower = Tower::new(chamber.width).with_pruning();
+        let (simulated, stats) =
+            aoc_profile::measure(|| get_tower_height_from(&chamber, tower, &pushes, rock_count));
+        let from_cycle = get_tower_height(&chamber, &pushes, rock_count);
+        println!(
+            "Check {} rocks: simulated {}, from cycle {} ({})",
+            rock_count,
+            simulated,
+            answer(&from_cycle),
+            stats
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_day17 {
+    use super::*;
+
+    const EXAMPLE1: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    fn shape(i: usize) -> Shape {
+        Chamber::puzzle().shapes[i].clone()
+    }
+
+    #[test]
+    fn test1_1() {
+        assert_eq!(solve_part1(EXAMPLE1, &Chamber::puzzle()), Ok(3068));
+    }
+
+    #[test]
+    fn test1_get_tower_height_1() {
+        let pushes = Push::from_str(EXAMPLE1);
+        let chamber = Chamber::puzzle();
+
+        assert_eq!(get_tower_height(&chamber, &pushes[..], 1), Ok(1));
+        assert_eq!(get_tower_height(&chamber, &pushes[..], 2), Ok(4));
+        assert_eq!(get_tower_height(&chamber, &pushes[..], 3), Ok(6));
+        assert_eq!(get_tower_height(&chamber, &pushes[..], 4), Ok(7));
+        assert_eq!(get_tower_height(&chamber, &pushes[..], 5), Ok(9));
+    }
+
+    #[test]
+    fn test1_get_tower_height_2() {
+        let pushes = Push::from_str(EXAMPLE1);
+
+        assert_eq!(
+            get_tower_height(&Chamber::puzzle(), &pushes[..], 2022),
+            Ok(3068)
+        );
+    }
+
+    #[test]
+    fn test1_get_tower_height_from_1() {
+        let pushes = Push::from_str(EXAMPLE1);
+
+        assert_eq!(
+            get_tower_height_from(&Chamber::puzzle(), Tower::new(7), &pushes[..], 2022),
+            3068
+        );
+    }
+
+    #[test]
+    fn test1_shape_from_str_1() {
+        assert_eq!(
+            Shape::from_str("####"),
+            Ok(Shape {
+                rows: vec![0b1111],
+                width: 4
+            })
+        );
+        assert_eq!(
+            Shape::from_str(".#.\n###\n.#."),
+            Ok(Shape {
+                rows: vec![0b010, 0b111, 0b010],
+                width: 3
+            })
+        );
+        assert_eq!(
+            Shape::from_str("..#\n\n..#\n..."),
+            Ok(Shape {
+                rows: vec![0b1, 0, 0b1],
+                width: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test1_shape_from_str_2() {
+        assert!(Shape::from_str("...").is_err());
+        assert!(Shape::from_str("#x#").is_err());
+        assert!(Shape::from_str(&"#".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn test1_chamber_1() {
+        let chamber = Chamber::puzzle();
+        assert_eq!(chamber.rock_count(), 5);
+        assert_eq!(format!("{}", chamber.shape(7)), "..#\n..#\n###");
+        let chamber = chamber.with_sequence("3, 4").unwrap();
+        assert_eq!(chamber.rock_count(), 2);
+        assert_eq!(chamber.shape(1).height(), 2);
+        assert!(Chamber::puzzle().with_sequence("5").is_err());
+        assert!(Chamber::new("##\n\n#x").is_err());
+        assert!(Chamber::new("########").is_err());
+        assert!(Chamber::puzzle().with_width(3).is_err());
+        assert!(Chamber::puzzle().with_width(65).is_err());
+        assert_eq!(
+            Chamber::puzzle().with_width(4).unwrap().spawn_x(&shape(0)),
+            0
+        );
+    }
+
+    #[test]
+    fn test1_chamber_2() {
+        let pushes = Push::from_str(EXAMPLE1);
+        let chamber = Chamber::new("###\n\n#\n#").unwrap();
+        let height = get_tower_height(&chamber, &pushes[..], 200000);
+        let brute = get_tower_height_from(&chamber, Tower::new(7), &pushes[..], 200000);
+        assert_eq!(height, Ok(brute));
+    }
+
+    #[test]
+    fn test1_chamber_width_1() {
+        let pushes = Push::from_str(EXAMPLE1);
+        for width in [4, 9, 40] {
+            let chamber = Chamber::puzzle().with_width(width).unwrap();
+            let height = get_tower_height(&chamber, &pushes[..], 20000);
+            let brute = get_tower_height_from(&chamber, Tower::new(width), &pushes[..], 20000);
+            assert_eq!(height, Ok(brute));
+        }
+    }
+
+    #[test]
+    fn test1_rock_row_1() {
+        let shape = shape(1);
+        assert_eq!(Rock::new(0, 0, &shape).row(0), 0b010);
+        assert_eq!(Rock::new(0, 0, &shape).row(1), 0b111);
+        assert_eq!(Rock::new(0, 0, &shape).row(2), 0b010);
+    }
+
+    #[test]
+    fn test1_rock_shifted_row_1() {
+        let line = shape(0);
+        assert_eq!(Rock::new(0, 0, &line).shifted_row(0), 0b0001111);
+        assert_eq!(Rock::new(1, 0, &line).shifted_row(0), 0b0011110);
+        assert_eq!(Rock::new(2, 0, &line).shifted_row(0), 0b0111100);
+        assert_eq!(Rock::new(3, 0, &line).shifted_row(0), 0b1111000);
+    }
+
+    #[test]
+    fn test1_rock_push_1() {
+        let line = shape(0);
+        let mut rock = Rock::new(2, 1, &line);
+        rock.apply_push(&Push::Right, 7);
+        assert_eq!(rock.x, 3);
+        rock.apply_push(&Push::Right, 7);
+        assert_eq!(rock.x, 3);
+        let vertical = shape(3);
+        let mut rock = Rock::new(5, 1, &vertical);
+        rock.apply_push(&Push::Right, 7);
+        assert_eq!(rock.x, 6);
+    }
+
+    #[test]
+    fn test1_rock_row_count_1() {
+        assert_eq!(Rock::new(0, 0, &shape(0)).row_count(), 1);
+        assert_eq!(Rock::new(0, 0, &shape(3)).row_count(), 4);
+        assert_eq!(Rock::new(0, 0, &shape(1)).row_count(), 3);
+        assert_eq!(Rock::new(0, 0, &shape(4)).row_count(), 2);
+    }
+
+    #[test]
+    fn test1_rock_push_overlap_1() {
+        let mut tower = Tower::new(7);
+        let square = shape(4);
+        tower.add_rock(&Rock::new(5, 1, &square));
+        let rev_l = shape(2);
+        let mut rock = Rock::new(2, 1, &rev_l);
+        assert_eq!(rock.overlaps_tower(&tower), false);
+        rock.apply_push(&Push::Right, 7);
+        assert_eq!(rock.overlaps_tower(&tower), true);
+    }
+
+    #[test]
+    fn test1_trace_1() {
+        let pushes = Push::from_str(EXAMPLE1);
+        let chamber = Chamber::puzzle();
+        let events = trace(&chamber, &pushes[..], 1);
+        let lines: Vec<String> = events.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "s 0 0 2 4",
+                "p 0 0 > +",
+                "f 0 3",
+                "p 0 1 > -",
+                "f 0 2",
+                "p 0 2 > -",
+                "f 0 1",
+                "p 0 3 < +",
+                "r 0 2 1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test1_trace_2() {
+        let pushes = Push::from_str(EXAMPLE1);
+        let chamber = Chamber::puzzle();
+        let events = trace(&chamber, &pushes[..], 2022);
+        assert_eq!(
+            parse_trace(&write_trace(&chamber, &events)),
+            Ok(events.clone())
+        );
+        assert_eq!(replay(&chamber, &events).unwrap().tower.height(), 3068);
+
+        let partial = replay(&chamber, &events[..3]).unwrap();
+        let rock = partial.falling.unwrap();
+        assert_eq!((rock.x, rock.y), (3, 3));
+        assert_eq!(partial.tower.height(), 0);
+    }
+
+    #[test]
+    fn test1_replay_1() {
+        let pushes = Push::from_str(EXAMPLE1);
+        let chamber = Chamber::puzzle();
+        let mut events = trace(&chamber, &pushes[..], 10);
+        events[20] = Event::Rest {
+            rock: 2,
+            x: 0,
+            y: 1,
+        };
+        assert!(replay(&chamber, &events).is_err());
+        let original = trace(&chamber, &pushes[..], 10);
+        assert_eq!(first_difference(&original, &events), Some(20));
+        assert_eq!(first_difference(&original, &original[..5]), Some(5));
+        assert_eq!(first_difference(&original, &original), None);
+        assert!(parse_trace("s 0 0 2\n").is_err());
+        assert!(parse_trace("p 0 0 > x\n").is_err());
+    }
+
+    #[test]
+    fn test1_picture_1() {
+        let pushes = Push::from_str(EXAMPLE1);
+        let chamber = Chamber::puzzle();
+        let events = trace(&chamber, &pushes[..], 3);
+        let third = events
+            .iter()
+            .position(|e| matches!(e, Event::Spawn { rock: 2, .. }))
+            .unwrap();
+        let picture = Picture::from_trace(&chamber, &events[..=third]).unwrap();
+        assert_eq!(
+            picture.to_string(),
+            "|....@..|
+|....@..|
+|..@@@..|
+|.......|
+|.......|
+|.......|
+|...#...|
+|..###..|
+|...#...|
+|..####.|
++-------+"
+        );
+        assert_eq!(picture.draw(Some(2)), "|....@..|\n|....@..|\n|~~~~~~~|");
+        assert_eq!(picture.rows[0][2], Cell::Rock(Some(0)));
+        assert_eq!(picture.rows[1][3], Cell::Rock(Some(1)));
+    }
+
+    #[test]
+    fn test1_picture_2() {
+        let pushes = Push::from_str(EXAMPLE1);
+        let chamber = Chamber::puzzle();
+        let drawn = picture(&chamber, &pushes[..], 10).to_string();
+        let mut tower = Tower::new(chamber.width);
+        let mut push_index = 0;
+        for r in 0..10 {
+            push_index = drop_rock(&chamber, r, &mut tower, &pushes[..], push_index).1;
+        }
+        assert_eq!(tower.to_string(), drawn);
+        assert!(drawn.starts_with("|....#..|\n|....#..|\n|....##.|\n|##..##.|"));
+
+        let image = picture(&chamber, &pushes[..], 10).ppm(2, 10);
+        let header = b"P6\n30 20\n255\n";
+        assert!(image.starts_with(header));
+        assert_eq!(image.len(), header.len() + 30 * 20 * 3);
+    }
+
+    #[test]
+    fn test2_1() {
+        assert_eq!(solve_part2(EXAMPLE1, &Chamber::puzzle()), Ok(1514285714288));
+    }
+
+    #[test]
+    fn test2_find_cycle_1() {
+        let pushes = Push::from_str(EXAMPLE1);
+        let (heights, cycle) = find_cycle(&Chamber::puzzle(), &pushes[..], 2022);
+        let cycle = cycle.unwrap();
+        assert_eq!(cycle.period, 35);
+        assert_eq!(cycle.height, 53);
+        assert_eq!(heights.len(), cycle.prefix + cycle.period + 1);
+        assert_eq!(cycle.extrapolate(&heights, 2022), 3068);
+
+        let (_, cycle) = find_cycle(&Chamber::puzzle(), &pushes[..], 10);
+        assert_eq!(cycle, None);
+    }
+
+    #[test]
+    fn test2_get_tower_height_within_1() {
+        // Without a cycle among the first rocks, only those rocks can be counted
+        let pushes = Push::from_str(EXAMPLE1);
+        let chamber = Chamber::puzzle();
+        assert_eq!(
+            get_tower_height_within(&chamber, &pushes[..], 1000000000000, 10),
+            Err("No cycle within 10 rocks".to_string())
+        );
+        assert_eq!(
+            get_tower_height_within(&chamber, &pushes[..], 2022, 10),
+            Err("No cycle within 10 rocks".to_string())
+        );
+        assert_eq!(get_tower_height_within(&chamber, &pushes[..], 5, 10), Ok(9));
+    }
+
+    #[test]
+    fn test2_find_cycle_2() {
+        let pushes = Push::from_str(EXAMPLE1);
+        let chamber = Chamber::new("#\n\n##\n##\n\n.#.\n###\n.#.")
+            .unwrap()
+            .with_sequence("2,1,0,0")
+            .unwrap()
+            .with_width(5)
+            .unwrap();
+        let (heights, cycle) = find_cycle(&chamber, &pushes[..], 10000);
+        let cycle = cycle.unwrap();
+        let brute = get_tower_height_from(&chamber, Tower::new(5), &pushes[..], 5000);
+        assert_eq!(cycle.extrapolate(&heights, 5000), brute);
+    }
+
+    #[test]
+    fn test2_tower_surface_1() {
+        let chamber = Chamber::puzzle();
+        let mut tower = Tower::new(7);
+        assert_eq!(tower.surface(10), Some(vec![]));
+        // A horizontal line over a gap, reached from the right
+        tower.add_rock(&Rock::new(0, 1, &chamber.shapes[4]));
+        tower.add_rock(&Rock::new(2, 2, &chamber.shapes[0]));
+        assert_eq!(tower.surface(10), Some(vec![0b1000000, 0b1111100]));
+        assert_eq!(tower.surface(1), None);
+        // Closing the gap with a vertical line leaves only the rows beside it
+        tower.add_rock(&Rock::new(6, 2, &chamber.shapes[3]));
+        assert_eq!(tower.surface(10), Some(vec![0b0111111; 3]));
+    }
+
+    #[test]
+    fn test2_tower_prune_1() {
+        let pushes = Push::from_str(EXAMPLE1);
+        let chamber = Chamber::puzzle();
+        let mut tower = Tower::new(7).with_pruning();
+        let mut push_index = 0;
+        for r in 0..20000 {
+            (_, push_index) = drop_rock(&chamber, r, &mut tower, &pushes[..], push_index);
+            assert!(tower.rows.len() < 4 * PRUNE_MIN_ROWS);
+        }
+        assert!(tower.y_offset > 0);
+        assert_eq!(
+            tower.height(),
+            get_tower_height_from(&chamber, Tower::new(7), &pushes[..], 20000)
+        );
+    }
+
+    #[test]
+    fn test2_tower_prune_2() {
+        let chamber = Chamber::puzzle();
+        let mut tower = Tower::new(7);
+        tower.add_rock(&Rock::new(0, 1, &chamber.shapes[4]));
+        tower.add_rock(&Rock::new(2, 2, &chamber.shapes[0]));
+        tower.add_rock(&Rock::new(6, 2, &chamber.shapes[3]));
+        tower.prune();
+        assert_eq!(tower.y_offset, 2);
+        assert_eq!(tower.height(), 5);
+        assert_eq!(tower.surface(10), Some(vec![0b0111111; 3]));
+    }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let chamber = Chamber::puzzle();
+        let mut repl = Repl {
+            chamber: &chamber,
+            pushes: Push::from_str(EXAMPLE1),
+        };
+        assert_eq!(repl.execute("height", &["2022"]), Ok("3068".to_string()));
+        assert_eq!(
+            repl.execute("draw", &["2", "2"]),
+            Ok("|...#...|\n|..###..|\n|~~~~~~~|".to_string())
+        );
+        assert_eq!(
+            repl.execute("cycle", &[]),
+            Ok(format!(
+                "Cycle {}",
+                find_cycle(&chamber, &repl.pushes, 2022).1.unwrap()
+            ))
+        );
+        assert!(repl.execute("height", &["x"]).is_err());
+        assert_eq!(repl.part1(), Ok("3068".to_string()));
+        assert_eq!(repl.part2(), Ok("1514285714288".to_string()));
+    }
+}
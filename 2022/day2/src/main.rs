@@ -0,0 +1,275 @@
+#[macro_use]
+extern crate lazy_static;
+extern crate regex;
+
+use std::env;
+use std::fs;
+
+#[repr(u8)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+enum Pick {
+    Rock = 1,
+    Scizzors = 2,
+    Paper = 3,
+}
+
+#[repr(u8)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+enum Outcome {
+    Lose = 0,
+    Draw = 3,
+    Win = 6,
+}
+
+impl Pick {
+    fn new(s: &str) -> Pick {
+        match s {
+            "A" | "X" => Pick::Rock,
+            "B" | "Y" => Pick::Paper,
+            "C" | "Z" => Pick::Scizzors,
+            _ => panic!("not valid pick"),
+        }
+    }
+
+    fn from_u8(value: u8) -> Pick {
+        match value {
+            1 => Pick::Rock,
+            2 => Pick::Scizzors,
+            3 => Pick::Paper,
+            _ => panic!("not valid pick value"),
+        }
+    }
+
+    fn play(&self, other: &Pick) -> Outcome {
+        if *self == *other {
+            Outcome::Draw
+        } else if (*self as u8 + 1) % 3 == (*other as u8) {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    fn score(&self) -> usize {
+        match *self {
+            Pick::Rock => 1,
+            Pick::Paper => 2,
+            Pick::Scizzors => 3,
+        }
+    }
+
+    fn pick_for_outcome_against_self(&self, outcome: &Outcome) -> Pick {
+        let pick_offset = match outcome {
+            Outcome::Win => 2,
+            Outcome::Lose => 1,
+            Outcome::Draw => 0,
+        };
+        Pick::from_u8((*self as u8 + pick_offset) % 3)
+    }
+}
+
+impl Outcome {
+    fn new(s: &str) -> Outcome {
+        match s {
+            "X" => Outcome::Lose,
+            "Y" => Outcome::Draw,
+            "Z" => Outcome::Win,
+            _ => panic!("{} is not valid outcome", s),
+        }
+    }
+
+    fn score(&self) -> usize {
+        *self as usize
+    }
+}
+
+// The opponent pick and the second column of each round
+fn parse_rounds(input: &str) -> Vec<(&str, &str)> {
+    lazy_static! {
+        static ref RE: regex::Regex = regex::Regex::new(r"([A-C]) ([X-Z])").unwrap();
+    }
+    input
+        .lines()
+        .filter(|l| l.trim().len() > 0)
+        .map(|line| {
+            if let Some(captures) = RE.captures(line) {
+                assert!(captures.len() == 3);
+                (
+                    captures.get(1).unwrap().as_str(),
+                    captures.get(2).unwrap().as_str(),
+                )
+            } else {
+                panic!("Failed to parse line: <{}>", line);
+            }
+        })
+        .collect()
+}
+
+fn score_as_pick(pick1_str: &str, pick2_str: &str) -> usize {
+    let pick1 = Pick::new(pick1_str);
+    let pick2 = Pick::new(pick2_str);
+    let outcome = pick2.play(&pick1);
+    pick2.score() + outcome.score()
+}
+
+fn score_as_outcome(pick1_str: &str, outcome_str: &str) -> usize {
+    let pick1 = Pick::new(pick1_str);
+    let outcome = Outcome::new(outcome_str);
+    let pick2 = pick1.pick_for_outcome_against_self(&outcome);
+    pick2.score() + outcome.score()
+}
+
+fn total_score(rounds: &[(&str, &str)], score: fn(&str, &str) -> usize) -> usize {
+    rounds
+        .iter()
+        .map(|(first, second)| score(first, second))
+        .sum()
+}
+
+fn solve_part1(input: &str) -> usize {
+    total_score(&parse_rounds(input), score_as_pick)
+}
+
+fn solve_part2(input: &str) -> usize {
+    total_score(&parse_rounds(input), score_as_outcome)
+}
+
+struct Repl<'a> {
+    rounds: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Repl<'a> {
+    fn new(input: &'a str) -> Repl<'a> {
+        Repl {
+            rounds: parse_rounds(input),
+        }
+    }
+}
+
+impl<'a> aoc_repl::Introspect for Repl<'a> {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![
+            (
+                "round",
+                "<index>",
+                "Score a round of the guide read as a pick and as an outcome",
+            ),
+            (
+                "play",
+                "<opponent> <you>",
+                "Score a round, with A-C and X-Z as picks",
+            ),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "round" => {
+                let index: usize = aoc_repl::arg(args, 0, "index")?;
+                let (first, second) = self.rounds.get(index).ok_or(format!(
+                    "No round {}, there are {}",
+                    index,
+                    self.rounds.len()
+                ))?;
+                Ok(format!(
+                    "{} {} scores {} as a pick and {} as an outcome",
+                    first,
+                    second,
+                    score_as_pick(first, second),
+                    score_as_outcome(first, second)
+                ))
+            }
+            "play" => {
+                let opponent: String = aoc_repl::arg(args, 0, "opponent")?;
+                let you: String = aoc_repl::arg(args, 1, "you")?;
+                if !["A", "B", "C"].contains(&opponent.as_str()) {
+                    return Err(format!("Expected A, B or C, got '{}'", opponent));
+                }
+                if !["X", "Y", "Z"].contains(&you.as_str()) {
+                    return Err(format!("Expected X, Y or Z, got '{}'", you));
+                }
+                let (opponent, you) = (Pick::new(&opponent), Pick::new(&you));
+                let outcome = you.play(&opponent);
+                Ok(format!(
+                    "{:?} against {:?} is {:?}, scoring {}",
+                    you,
+                    opponent,
+                    outcome,
+                    you.score() + outcome.score()
+                ))
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(total_score(&self.rounds, score_as_pick).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(total_score(&self.rounds, score_as_outcome).to_string())
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
+    let filename = args
+        .get(1)
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
+
+    println!("Reading input from {}", filename);
+    let input = fs::read_to_string(filename).expect("Failed to read file");
+
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
+    println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
+
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
+    println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
+}
+
+#[cfg(test)]
+mod tests_day2 {
+    use super::*;
+
+    const EXAMPLE1: &str = "
+A Y
+B X
+C Z";
+
+    #[test]
+    fn test1_1() {
+        assert_eq!(solve_part1(EXAMPLE1), 15);
+    }
+
+    #[test]
+    fn test2_1() {
+        assert_eq!(solve_part2(EXAMPLE1), 12);
+    }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert_eq!(
+            repl.execute("play", &["A", "Y"]),
+            Ok("Paper against Rock is Win, scoring 8".to_string())
+        );
+        assert!(repl.execute("play", &["X", "A"]).is_err());
+        assert_eq!(
+            repl.execute("round", &["1"]),
+            Ok("B X scores 1 as a pick and 1 as an outcome".to_string())
+        );
+        assert!(repl.execute("round", &["3"]).is_err());
+        assert_eq!(repl.part1(), Ok("15".to_string()));
+        assert_eq!(repl.part2(), Ok("12".to_string()));
+    }
+}
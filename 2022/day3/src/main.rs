@@ -0,0 +1,198 @@
+use std::env;
+use std::fs;
+
+const LOWER_A_VALUE: u32 = ('a' as char) as u32;
+const LOWER_Z_VALUE: u32 = ('z' as char) as u32;
+const UPPER_A_VALUE: u32 = ('A' as char) as u32;
+const UPPER_Z_VALUE: u32 = ('Z' as char) as u32;
+
+fn get_value(c: char) -> Option<u32> {
+    let value = c as u32;
+    if value >= LOWER_A_VALUE && value <= LOWER_Z_VALUE {
+        Some(value - LOWER_A_VALUE + 1)
+    } else if value >= UPPER_A_VALUE && value <= UPPER_Z_VALUE {
+        Some(value - UPPER_A_VALUE + 27)
+    } else {
+        None
+    }
+}
+
+fn parse_rucksacks(input: &str) -> Vec<&str> {
+    input.lines().filter(|l| !l.trim().is_empty()).collect()
+}
+
+// The item type found in both compartments of a rucksack
+fn shared_item(rucksack: &str) -> Option<char> {
+    let compartment_size = rucksack.len() / 2;
+    let compartment1 = &rucksack[0..compartment_size];
+    let compartment2 = &rucksack[compartment_size..];
+
+    compartment1
+        .chars()
+        .find(|c| compartment2.contains(|c2| &c2 == c))
+}
+
+fn shared_priority_sum(rucksacks: &[&str]) -> u32 {
+    let mut sum = 0;
+    for rucksack in rucksacks {
+        let prio = shared_item(rucksack)
+            .map(|c| get_value(c).expect("Unexpected char"))
+            .unwrap_or(0);
+
+        sum += prio
+    }
+    sum
+}
+
+fn badge_priority_sum(rucksacks: &[&str]) -> u32 {
+    let mut sum = 0;
+    let mut lines_iter = rucksacks.iter();
+    while let Some(line0) = lines_iter.next() {
+        let line1 = lines_iter.next().unwrap();
+        let line2 = lines_iter.next().unwrap();
+        let badge_prio = line0
+            .chars()
+            .filter(|c| line1.contains(|c1| &c1 == c))
+            .filter(|c| line2.contains(|c2| &c2 == c))
+            .take(1)
+            .map(|badge| get_value(badge).expect("Unexpected char"))
+            .next()
+            .unwrap();
+
+        sum += badge_prio;
+    }
+    sum
+}
+
+fn solve_part1(input: &str) -> u32 {
+    shared_priority_sum(&parse_rucksacks(input))
+}
+
+fn solve_part2(input: &str) -> u32 {
+    badge_priority_sum(&parse_rucksacks(input))
+}
+
+struct Repl<'a> {
+    rucksacks: Vec<&'a str>,
+}
+
+impl<'a> Repl<'a> {
+    fn new(input: &'a str) -> Repl<'a> {
+        Repl {
+            rucksacks: parse_rucksacks(input),
+        }
+    }
+}
+
+impl<'a> aoc_repl::Introspect for Repl<'a> {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![
+            (
+                "rucksack",
+                "<index>",
+                "Show the compartments of a rucksack and the item type in both",
+            ),
+            ("priority", "<item>", "Priority of an item type"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "rucksack" => {
+                let index: usize = aoc_repl::arg(args, 0, "index")?;
+                let rucksack = self.rucksacks.get(index).ok_or(format!(
+                    "No rucksack {}, there are {}",
+                    index,
+                    self.rucksacks.len()
+                ))?;
+                let (compartment1, compartment2) = rucksack.split_at(rucksack.len() / 2);
+                let shared = match shared_item(rucksack) {
+                    Some(c) => format!("{} with priority {}", c, get_value(c).unwrap_or(0)),
+                    None => String::from("nothing"),
+                };
+                Ok(format!(
+                    "{} {} share {}",
+                    compartment1, compartment2, shared
+                ))
+            }
+            "priority" => {
+                let item: char = aoc_repl::arg(args, 0, "item")?;
+                get_value(item)
+                    .map(|value| value.to_string())
+                    .ok_or(format!("'{}' is not an item type", item))
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(shared_priority_sum(&self.rucksacks).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(badge_priority_sum(&self.rucksacks).to_string())
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
+    let filename = args
+        .get(1)
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
+
+    println!("Reading input from {}", filename);
+    let input = fs::read_to_string(filename).expect("Failed to read file");
+
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
+    println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
+
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
+    println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
+}
+
+#[cfg(test)]
+mod tests_day3 {
+    use super::*;
+
+    const EXAMPLE1: &str = "
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn test1_1() {
+        assert_eq!(solve_part1(EXAMPLE1), 157);
+    }
+
+    #[test]
+    fn test2_1() {
+        assert_eq!(solve_part2(EXAMPLE1), 70);
+    }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert_eq!(repl.execute("priority", &["p"]), Ok("16".to_string()));
+        assert_eq!(repl.execute("priority", &["L"]), Ok("38".to_string()));
+        assert!(repl.execute("priority", &["1"]).is_err());
+        assert_eq!(
+            repl.execute("rucksack", &["0"]),
+            Ok("vJrwpWtwJgWr hcsFMMfFFhFp share p with priority 16".to_string())
+        );
+        assert_eq!(repl.part1(), Ok("157".to_string()));
+        assert_eq!(repl.part2(), Ok("70".to_string()));
+    }
+}
@@ -0,0 +1,184 @@
+#[macro_use]
+extern crate lazy_static;
+extern crate regex;
+
+use aoc_interval::Interval;
+use std::env;
+use std::fs;
+
+#[derive(Debug)]
+struct Pair {
+    r1: Interval<usize>,
+    r2: Interval<usize>,
+}
+
+impl Pair {
+    fn new(r1: Interval<usize>, r2: Interval<usize>) -> Pair {
+        Pair { r1, r2 }
+    }
+
+    fn contains(&self) -> bool {
+        self.r1.contains_interval(&self.r2) || self.r2.contains_interval(&self.r1)
+    }
+
+    fn overlaps(&self) -> bool {
+        self.r1.overlaps(&self.r2)
+    }
+}
+
+fn parse_pairs(input: &str) -> Vec<Pair> {
+    lazy_static! {
+        static ref RE: regex::Regex = regex::Regex::new(r"(\d*)-(\d*),(\d*)-(\d*)").unwrap();
+    }
+    input
+        .lines()
+        .filter_map(|l| if l.len() > 0 { Some(l) } else { None })
+        .map(|l| {
+            let captures = RE.captures(l).unwrap();
+            assert_eq!(captures.len(), 5);
+            let digits = [
+                captures.get(1).unwrap().as_str().parse::<usize>().unwrap(),
+                captures.get(2).unwrap().as_str().parse::<usize>().unwrap(),
+                captures.get(3).unwrap().as_str().parse::<usize>().unwrap(),
+                captures.get(4).unwrap().as_str().parse::<usize>().unwrap(),
+            ];
+            Pair::new(
+                Interval::inclusive(digits[0], digits[1]),
+                Interval::inclusive(digits[2], digits[3]),
+            )
+        })
+        .collect()
+}
+
+fn count_containing(pairs: &[Pair]) -> usize {
+    pairs.iter().filter(|p| p.contains()).count()
+}
+
+fn count_overlapping(pairs: &[Pair]) -> usize {
+    pairs.iter().filter(|p| p.overlaps()).count()
+}
+
+fn solve_part1(input: &str) -> usize {
+    count_containing(&parse_pairs(input))
+}
+
+fn solve_part2(input: &str) -> usize {
+    count_overlapping(&parse_pairs(input))
+}
+
+struct Repl {
+    pairs: Vec<Pair>,
+}
+
+impl Repl {
+    fn new(input: &str) -> Repl {
+        Repl {
+            pairs: parse_pairs(input),
+        }
+    }
+}
+
+impl aoc_repl::Introspect for Repl {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![(
+            "pair",
+            "<index>",
+            "Whether one assignment of a pair contains or overlaps the other",
+        )]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "pair" => {
+                let index: usize = aoc_repl::arg(args, 0, "index")?;
+                let pair = self.pairs.get(index).ok_or(format!(
+                    "No pair {}, there are {}",
+                    index,
+                    self.pairs.len()
+                ))?;
+                Ok(format!(
+                    "{:?} {:?} contains: {}, overlaps: {}",
+                    pair.r1,
+                    pair.r2,
+                    pair.contains(),
+                    pair.overlaps()
+                ))
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(count_containing(&self.pairs).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(count_overlapping(&self.pairs).to_string())
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
+    let filename = args
+        .get(1)
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
+
+    println!("Reading input from {}", filename);
+    let input = fs::read_to_string(filename).expect("Failed to read file");
+
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
+    println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
+
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
+    println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
+}
+
+#[cfg(test)]
+mod tests_day4 {
+    use super::*;
+
+    const EXAMPLE1: &str = "
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+    #[test]
+    fn test1_1() {
+        assert_eq!(solve_part1(EXAMPLE1), 2);
+    }
+
+    #[test]
+    fn test2_1() {
+        assert_eq!(solve_part2(EXAMPLE1), 4);
+    }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert_eq!(
+            repl.execute("pair", &["3"]),
+            Ok("[2, 9) [3, 8) contains: true, overlaps: true".to_string())
+        );
+        assert_eq!(
+            repl.execute("pair", &["0"]),
+            Ok("[2, 5) [6, 9) contains: false, overlaps: false".to_string())
+        );
+        assert!(repl.execute("pair", &["6"]).is_err());
+        assert_eq!(repl.part1(), Ok("2".to_string()));
+        assert_eq!(repl.part2(), Ok("4".to_string()));
+    }
+}
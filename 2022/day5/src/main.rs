@@ -58,7 +58,7 @@ impl FromStr for Row {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone)]
 struct Stacks {
     stacks: Vec<String>,
 }
@@ -175,10 +175,7 @@ impl FromStr for Move {
     }
 }
 
-fn solve_part1(input: &str) -> String {
-    lazy_static! {
-        static ref RE: regex::Regex = regex::Regex::new(r"(\d*) ([a-z]*)").unwrap();
-    }
+fn parse_input(input: &str) -> (Stacks, Vec<Move>) {
     let rows: Vec<Row> = input
         .lines()
         .filter_map(|l| if l.len() > 0 { Some(l) } else { None })
@@ -193,15 +190,19 @@ fn solve_part1(input: &str) -> String {
         .map(|l| Move::from_str(l).unwrap())
         .collect();
 
-    let mut stacks = Stacks::from_rows(&rows);
-    println!("before");
-    println!("{:?}", stacks);
-    for m in &moves {
-        println!("{:?}", m);
-        stacks.apply_move(m);
-        println!("{:?}", stacks);
+    (Stacks::from_rows(&rows), moves)
+}
+
+// Stacks after the moves, made by a crane moving crates with apply
+fn rearrange(stacks: &Stacks, moves: &[Move], apply: fn(&mut Stacks, &Move)) -> Stacks {
+    let mut stacks = stacks.clone();
+    for m in moves {
+        apply(&mut stacks, m);
     }
+    stacks
+}
 
+fn top_crates(stacks: &Stacks) -> String {
     stacks
         .stacks
         .iter()
@@ -209,38 +210,84 @@ fn solve_part1(input: &str) -> String {
         .collect::<String>()
 }
 
+fn solve_part1(input: &str) -> String {
+    let (stacks, moves) = parse_input(input);
+    top_crates(&rearrange(&stacks, &moves, Stacks::apply_move))
+}
+
 fn solve_part2(input: &str) -> String {
-    lazy_static! {
-        static ref RE: regex::Regex = regex::Regex::new(r"(\d*) ([a-z]*)").unwrap();
+    let (stacks, moves) = parse_input(input);
+    top_crates(&rearrange(&stacks, &moves, Stacks::apply_move2))
+}
+
+struct Repl {
+    stacks: Stacks,
+    moves: Vec<Move>,
+}
+
+impl Repl {
+    fn new(input: &str) -> Repl {
+        let (stacks, moves) = parse_input(input);
+        Repl { stacks, moves }
     }
-    let rows: Vec<Row> = input
-        .lines()
-        .filter_map(|l| if l.len() > 0 { Some(l) } else { None })
-        .map_while(|l| Row::from_str(l).ok())
-        .collect();
-    let row_count = rows.len();
+}
 
-    let moves: Vec<Move> = input
-        .lines()
-        .filter_map(|l| if l.len() > 0 { Some(l) } else { None })
-        .skip(row_count + 1)
-        .map(|l| Move::from_str(l).unwrap())
-        .collect();
+impl aoc_repl::Introspect for Repl {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![
+            (
+                "stacks",
+                "[moves]",
+                "Print the stacks after the first moves of the CrateMover 9000",
+            ),
+            ("move", "<index>", "Print a move"),
+        ]
+    }
 
-    let mut stacks = Stacks::from_rows(&rows);
-    println!("before");
-    println!("{:?}", stacks);
-    for m in &moves {
-        println!("{:?}", m);
-        stacks.apply_move2(m);
-        println!("{:?}", stacks);
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "stacks" => {
+                let count: usize = aoc_repl::arg_or(args, 0, "moves", 0)?;
+                let moves = self
+                    .moves
+                    .get(..count)
+                    .ok_or(format!("There are only {} moves", self.moves.len()))?;
+                let stacks = rearrange(&self.stacks, moves, Stacks::apply_move);
+                Ok(format!("{:?}", stacks).trim_end().to_string())
+            }
+            "move" => {
+                let index: usize = aoc_repl::arg(args, 0, "index")?;
+                let m = self.moves.get(index).ok_or(format!(
+                    "No move {}, there are {}",
+                    index,
+                    self.moves.len()
+                ))?;
+                Ok(format!(
+                    "move {} from {} to {}",
+                    m.count,
+                    m.from + 1,
+                    m.to + 1
+                ))
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
     }
 
-    stacks
-        .stacks
-        .iter()
-        .map(|s| s.chars().last().unwrap_or(' '))
-        .collect::<String>()
+    fn part1(&self) -> Result<String, String> {
+        Ok(top_crates(&rearrange(
+            &self.stacks,
+            &self.moves,
+            Stacks::apply_move,
+        )))
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(top_crates(&rearrange(
+            &self.stacks,
+            &self.moves,
+            Stacks::apply_move2,
+        )))
+    }
 }
 
 fn main() {
@@ -248,16 +295,24 @@ fn main() {
     const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
     let filename = args
         .get(1)
-        .expect(format!("Usage: {} input-filename", PACKAGE_NAME.unwrap()).as_str());
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
 
     println!("Reading input from {}", filename);
     let input = fs::read_to_string(filename).expect("Failed to read file");
 
-    let answer1 = solve_part1(&input);
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
     println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
 
-    let answer2 = solve_part2(&input);
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
     println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
 }
 
 #[cfg(test)]
@@ -331,4 +386,25 @@ move 1 from 1 to 2
     fn test2_1() {
         assert_eq!(solve_part2(EXAMPLE1), "MCD");
     }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert_eq!(
+            repl.execute("stacks", &[]),
+            Ok(" .  [D]  .  \n[N] [C]  .  \n[Z] [M] [P]".to_string())
+        );
+        assert_eq!(
+            repl.execute("stacks", &["1"]),
+            Ok("[D]  .   .  \n[N] [C]  .  \n[Z] [M] [P]".to_string())
+        );
+        assert!(repl.execute("stacks", &["5"]).is_err());
+        assert_eq!(
+            repl.execute("move", &["1"]),
+            Ok("move 3 from 1 to 3".to_string())
+        );
+        assert_eq!(repl.part1(), Ok("CMZ".to_string()));
+        assert_eq!(repl.part2(), Ok("MCD".to_string()));
+    }
 }
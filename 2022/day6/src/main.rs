@@ -30,21 +30,69 @@ fn solve_part2(input: &str) -> usize {
     find_marker_pos(input, 14)
 }
 
+struct Repl<'a> {
+    datastream: &'a str,
+}
+
+impl<'a> Repl<'a> {
+    fn new(input: &'a str) -> Repl<'a> {
+        Repl {
+            datastream: input.trim(),
+        }
+    }
+}
+
+impl<'a> aoc_repl::Introspect for Repl<'a> {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![(
+            "marker",
+            "<length>",
+            "Characters read before a marker of distinct ones",
+        )]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "marker" => {
+                let length: usize = aoc_repl::arg(args, 0, "length")?;
+                Ok(find_marker_pos(self.datastream, length).to_string())
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(find_marker_pos(self.datastream, 4).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(find_marker_pos(self.datastream, 14).to_string())
+    }
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
     const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
     let filename = args
         .get(1)
-        .expect(format!("Usage: {} input-filename", PACKAGE_NAME.unwrap()).as_str());
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
 
     println!("Reading input from {}", filename);
     let input = fs::read_to_string(filename).expect("Failed to read file");
 
-    let answer1 = solve_part1(&input);
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
     println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
 
-    let answer2 = solve_part2(&input);
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
     println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
 }
 
 #[cfg(test)]
@@ -72,4 +120,14 @@ mod tests_day6 {
     fn test2_1() {
         assert_eq!(solve_part2(EXAMPLE2_1), 0);
     }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1_1);
+        assert_eq!(repl.execute("marker", &["4"]), Ok("7".to_string()));
+        assert_eq!(repl.execute("marker", &["14"]), Ok("19".to_string()));
+        assert_eq!(repl.part1(), Ok("7".to_string()));
+        assert_eq!(repl.part2(), Ok("19".to_string()));
+    }
 }
@@ -0,0 +1,1202 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+
+#[derive(Default)]
+struct Dir {
+    name: String,
+    parent: Option<usize>,
+    dirs: BTreeMap<String, usize>,
+    files: BTreeMap<String, usize>,
+    listed: bool,
+}
+
+// Directories by index with the root first, a directory always comes after its parent
+struct FileSystem {
+    dirs: Vec<Dir>,
+}
+
+impl FileSystem {
+    const ROOT: usize = 0;
+
+    fn new() -> FileSystem {
+        FileSystem {
+            dirs: vec![Dir::default()],
+        }
+    }
+
+    // Index of the named subdirectory, which is created unless it already exists
+    fn mkdir(&mut self, parent: usize, name: &str) -> usize {
+        if let Some(&dir) = self.dirs[parent].dirs.get(name) {
+            return dir;
+        }
+        let dir = self.dirs.len();
+        self.dirs.push(Dir {
+            name: name.to_owned(),
+            parent: Some(parent),
+            ..Dir::default()
+        });
+        self.dirs[parent].dirs.insert(name.to_owned(), dir);
+        dir
+    }
+
+    fn add_file(&mut self, dir: usize, name: &str, size: usize) {
+        self.dirs[dir].files.insert(name.to_owned(), size);
+    }
+
+    fn parent(&self, dir: usize) -> Option<usize> {
+        self.dirs[dir].parent
+    }
+
+    fn path(&self, dir: usize) -> String {
+        match self.dirs[dir].parent {
+            None => "/".to_owned(),
+            Some(FileSystem::ROOT) => format!("/{}", self.dirs[dir].name),
+            Some(parent) => format!("{}/{}", self.path(parent), self.dirs[dir].name),
+        }
+    }
+
+    // Names of the directories from the root down to dir
+    fn names(&self, dir: usize) -> Vec<&str> {
+        let mut names = Vec::new();
+        let mut d = dir;
+        while let Some(parent) = self.dirs[d].parent {
+            names.push(self.dirs[d].name.as_str());
+            d = parent;
+        }
+        names.reverse();
+        names
+    }
+
+    fn find(&self, path: &str) -> Option<usize> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(FileSystem::ROOT, |dir, name| {
+                self.dirs[dir].dirs.get(name).copied()
+            })
+    }
+
+    #[cfg(test)]
+    fn dir_count(&self) -> usize {
+        self.dirs.len()
+    }
+
+    fn children(&self, dir: usize) -> impl Iterator<Item = (&str, usize)> {
+        self.dirs[dir]
+            .dirs
+            .iter()
+            .map(|(name, &d)| (name.as_str(), d))
+    }
+
+    fn files(&self, dir: usize) -> impl Iterator<Item = (&str, usize)> {
+        self.dirs[dir]
+            .files
+            .iter()
+            .map(|(name, &size)| (name.as_str(), size))
+    }
+
+    // Total size of each directory, including everything below it, by index
+    fn sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = self
+            .dirs
+            .iter()
+            .map(|dir| dir.files.values().sum())
+            .collect();
+        for dir in (1..self.dirs.len()).rev() {
+            let parent = self.dirs[dir].parent.unwrap();
+            sizes[parent] += sizes[dir];
+        }
+        sizes
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Target {
+    Root,
+    Up,
+    Dir(String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Entry {
+    Dir(String),
+    File(String, usize),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Cd(Target),
+    Ls(Vec<Entry>),
+}
+
+// Everything that can be wrong with a transcript, by line number starting at 1
+#[derive(Debug, PartialEq, Eq)]
+enum TranscriptError {
+    UnknownCommand {
+        line: usize,
+        command: String,
+    },
+    BadArguments {
+        line: usize,
+        command: String,
+    },
+    BadEntry {
+        line: usize,
+        text: String,
+    },
+    OutputWithoutLs {
+        line: usize,
+    },
+    NotListed {
+        line: usize,
+        name: String,
+        dir: String,
+    },
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranscriptError::UnknownCommand { line, command } => {
+                write!(f, "Line {}: Unknown command '{}'", line, command)
+            }
+            TranscriptError::BadArguments { line, command } => {
+                write!(f, "Line {}: Bad arguments to {}", line, command)
+            }
+            TranscriptError::BadEntry { line, text } => {
+                write!(
+                    f,
+                    "Line {}: Expected dir or file in listing, got '{}'",
+                    line, text
+                )
+            }
+            TranscriptError::OutputWithoutLs { line } => {
+                write!(f, "Line {}: Output without a preceding ls", line)
+            }
+            TranscriptError::NotListed { line, name, dir } => {
+                write!(
+                    f,
+                    "Line {}: No directory '{}' listed in {}",
+                    line, name, dir
+                )
+            }
+        }
+    }
+}
+
+fn parse_entry(line: usize, text: &str) -> Result<Entry, TranscriptError> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    match words[..] {
+        ["dir", name] => Ok(Entry::Dir(name.to_owned())),
+        [size, name] => match size.parse::<usize>() {
+            Ok(size) => Ok(Entry::File(name.to_owned(), size)),
+            Err(_) => Err(TranscriptError::BadEntry {
+                line,
+                text: text.to_owned(),
+            }),
+        },
+        _ => Err(TranscriptError::BadEntry {
+            line,
+            text: text.to_owned(),
+        }),
+    }
+}
+
+// Commands with the line they are on, names can be anything but whitespace
+fn parse(input: &str) -> Result<Vec<(usize, Command)>, TranscriptError> {
+    let mut commands: Vec<(usize, Command)> = Vec::new();
+    for (i, text) in input.lines().enumerate() {
+        let line = i + 1;
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let command = match words[..] {
+            [] => continue,
+            ["$", "cd", "/"] => Command::Cd(Target::Root),
+            ["$", "cd", ".."] => Command::Cd(Target::Up),
+            ["$", "cd", name] => Command::Cd(Target::Dir(name.to_owned())),
+            ["$", "ls"] => Command::Ls(Vec::new()),
+            ["$", command @ ("cd" | "ls"), ..] => {
+                return Err(TranscriptError::BadArguments {
+                    line,
+                    command: command.to_owned(),
+                })
+            }
+            ["$", command, ..] => {
+                return Err(TranscriptError::UnknownCommand {
+                    line,
+                    command: command.to_owned(),
+                })
+            }
+            ["$"] => {
+                return Err(TranscriptError::UnknownCommand {
+                    line,
+                    command: String::new(),
+                })
+            }
+            _ => match commands.last_mut() {
+                Some((_, Command::Ls(entries))) => {
+                    entries.push(parse_entry(line, text)?);
+                    continue;
+                }
+                _ => return Err(TranscriptError::OutputWithoutLs { line }),
+            },
+        };
+        commands.push((line, command));
+    }
+    Ok(commands)
+}
+
+// Runs the commands of a transcript, building the file system from what ls prints.
+// Once a directory has been listed, cd only goes to the subdirectories in its listing.
+fn interpret(input: &str) -> Result<FileSystem, TranscriptError> {
+    let mut fs = FileSystem::new();
+    let mut cwd = FileSystem::ROOT;
+    for (line, command) in parse(input)? {
+        match command {
+            Command::Cd(Target::Root) => cwd = FileSystem::ROOT,
+            // Like a shell, cd .. at the root stays there
+            Command::Cd(Target::Up) => cwd = fs.parent(cwd).unwrap_or(FileSystem::ROOT),
+            Command::Cd(Target::Dir(name)) => {
+                if fs.dirs[cwd].listed && !fs.dirs[cwd].dirs.contains_key(&name) {
+                    return Err(TranscriptError::NotListed {
+                        line,
+                        name,
+                        dir: fs.path(cwd),
+                    });
+                }
+                cwd = fs.mkdir(cwd, &name);
+            }
+            Command::Ls(entries) => {
+                for entry in entries {
+                    match entry {
+                        Entry::Dir(name) => {
+                            fs.mkdir(cwd, &name);
+                        }
+                        Entry::File(name, size) => fs.add_file(cwd, &name, size),
+                    }
+                }
+                fs.dirs[cwd].listed = true;
+            }
+        }
+    }
+    Ok(fs)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortBy {
+    Name,
+    // Largest first
+    Size,
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<SortBy, String> {
+        match s {
+            "name" => Ok(SortBy::Name),
+            "size" => Ok(SortBy::Size),
+            _ => Err(format!("Unknown sort order '{}', expected name or size", s)),
+        }
+    }
+}
+
+// How the tree and du reports are printed
+struct Report {
+    sort: SortBy,
+    // Depth of the deepest directory shown, the root is at depth 0
+    max_depth: Option<usize>,
+    human: bool,
+}
+
+impl Default for Report {
+    fn default() -> Report {
+        Report {
+            sort: SortBy::Name,
+            max_depth: None,
+            human: false,
+        }
+    }
+}
+
+// Size rounded up to one decimal below 10 and to whole units above, like du -h
+fn human_size(size: usize) -> String {
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < 4 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    let suffix = ["", "K", "M", "G", "T"][unit];
+    if unit == 0 {
+        format!("{}", size)
+    } else if value < 10.0 {
+        format!("{:.1}{}", (value * 10.0).ceil() / 10.0, suffix)
+    } else {
+        format!("{}{}", value.ceil(), suffix)
+    }
+}
+
+impl Report {
+    fn size(&self, size: usize) -> String {
+        match self.human {
+            true => human_size(size),
+            false => size.to_string(),
+        }
+    }
+
+    fn expands(&self, depth: usize) -> bool {
+        self.max_depth.is_none_or(|max_depth| depth < max_depth)
+    }
+
+    // The file system as printed in the puzzle, with the size of every directory
+    fn tree(&self, fs: &FileSystem) -> String {
+        let sizes = fs.sizes();
+        let mut lines = Vec::new();
+        self.tree_lines(fs, &sizes, FileSystem::ROOT, 0, &mut lines);
+        lines.join("\n")
+    }
+
+    fn tree_lines(
+        &self,
+        fs: &FileSystem,
+        sizes: &[usize],
+        dir: usize,
+        depth: usize,
+        lines: &mut Vec<String>,
+    ) {
+        let name = match dir {
+            FileSystem::ROOT => "/",
+            _ => &fs.dirs[dir].name,
+        };
+        let indent = "  ".repeat(depth);
+        let size = self.size(sizes[dir]);
+        lines.push(format!("{}- {} (dir, size={})", indent, name, size));
+        if !self.expands(depth) {
+            return;
+        }
+
+        let mut entries: Vec<(&str, usize, Option<usize>)> = fs
+            .children(dir)
+            .map(|(name, d)| (name, sizes[d], Some(d)))
+            .chain(fs.files(dir).map(|(name, size)| (name, size, None)))
+            .collect();
+        match self.sort {
+            SortBy::Name => entries.sort_by_key(|&(name, _, _)| name),
+            SortBy::Size => entries.sort_by_key(|&(name, size, _)| (Reverse(size), name)),
+        }
+        for (name, size, d) in entries {
+            match d {
+                Some(d) => self.tree_lines(fs, sizes, d, depth + 1, lines),
+                None => lines.push(format!(
+                    "{}  - {} (file, size={})",
+                    indent,
+                    name,
+                    self.size(size)
+                )),
+            }
+        }
+    }
+
+    // Size and path of every directory, subdirectories before their parent as du prints them
+    fn du(&self, fs: &FileSystem) -> String {
+        let sizes = fs.sizes();
+        let mut dirs = Vec::new();
+        self.du_dirs(fs, FileSystem::ROOT, 0, &mut dirs);
+        if self.sort == SortBy::Size {
+            dirs.sort_by_key(|&d| Reverse(sizes[d]));
+        }
+        dirs.iter()
+            .map(|&d| format!("{}\t{}", self.size(sizes[d]), fs.path(d)))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn du_dirs(&self, fs: &FileSystem, dir: usize, depth: usize, dirs: &mut Vec<usize>) {
+        if self.expands(depth) {
+            for (_, d) in fs.children(dir) {
+                self.du_dirs(fs, d, depth + 1, dirs);
+            }
+        }
+        dirs.push(dir);
+    }
+}
+
+// Space on the device and how much of it needs to be free for the update
+struct Disk {
+    size: usize,
+    required: usize,
+}
+
+impl Disk {
+    fn puzzle() -> Disk {
+        Disk {
+            size: 70000000,
+            required: 30000000,
+        }
+    }
+
+    // Smallest directory that frees up enough space when deleted, None if there already is
+    fn dir_to_delete(&self, fs: &FileSystem) -> Result<Option<usize>, String> {
+        let sizes = fs.sizes();
+        let used = sizes[FileSystem::ROOT];
+        if used > self.size {
+            return Err(format!("{} used on a disk of {}", used, self.size));
+        }
+        if self.required > self.size {
+            return Err(format!(
+                "{} required on a disk of {}",
+                self.required, self.size
+            ));
+        }
+        let free = self.size - used;
+        if free >= self.required {
+            return Ok(None);
+        }
+        let min_size_to_delete = self.required - free;
+        Ok((0..sizes.len())
+            .filter(|&d| sizes[d] >= min_size_to_delete)
+            .min_by_key(|&d| sizes[d]))
+    }
+}
+
+// Reads a tree as printed in the puzzle, or by the tree report, with two spaces per level
+fn parse_tree(input: &str) -> Result<FileSystem, String> {
+    let mut fs = FileSystem::new();
+    let mut stack: Vec<usize> = Vec::new();
+    for (i, text) in input.lines().enumerate() {
+        if text.trim().is_empty() {
+            continue;
+        }
+        let error = |message: &str| format!("Line {}: {}", i + 1, message);
+        let trimmed = text.trim_start_matches(' ');
+        let depth = (text.len() - trimmed.len()) / 2;
+        let (name, kind) = trimmed
+            .strip_prefix("- ")
+            .and_then(|t| t.strip_suffix(')'))
+            .and_then(|t| t.rsplit_once(" ("))
+            .ok_or(error(&format!(
+                "Expected '- name (dir)' or '- name (file, size=n)', got '{}'",
+                text
+            )))?;
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(error(&format!("Bad name '{}'", name)));
+        }
+        if depth > stack.len() || (depth == 0) != stack.is_empty() {
+            return Err(error("Bad indentation"));
+        }
+        stack.truncate(depth);
+        let parent = stack.last().copied();
+        let mut fields = kind.split(", ");
+        match (fields.next(), parent) {
+            (Some("dir"), None) if name == "/" => stack.push(FileSystem::ROOT),
+            (Some("dir"), Some(parent)) => stack.push(fs.mkdir(parent, name)),
+            (Some("file"), Some(parent)) => {
+                let size = fields
+                    .find_map(|f| f.strip_prefix("size="))
+                    .and_then(|size| size.parse::<usize>().ok())
+                    .ok_or(error("Expected size=n for file"))?;
+                fs.add_file(parent, name, size);
+            }
+            (_, None) => return Err(error("Expected the root to be '- / (dir)'")),
+            _ => return Err(error(&format!("Unknown kind '{}'", kind))),
+        }
+    }
+    Ok(fs)
+}
+
+// Reads a directory on disk, symbolic links are not followed
+fn scan(path: &Path) -> Result<FileSystem, String> {
+    let mut file_system = FileSystem::new();
+    scan_dir(path, &mut file_system, FileSystem::ROOT)?;
+    Ok(file_system)
+}
+
+fn scan_dir(path: &Path, file_system: &mut FileSystem, dir: usize) -> Result<(), String> {
+    let error = |e: std::io::Error| format!("{}: {}", path.display(), e);
+    for entry in fs::read_dir(path).map_err(error)? {
+        let entry = entry.map_err(error)?;
+        let name = entry.file_name();
+        let name = match name.to_str() {
+            Some(name) if !name.contains(char::is_whitespace) => name,
+            _ => {
+                let path = entry.path();
+                return Err(format!(
+                    "{}: Name can not be in a transcript",
+                    path.display()
+                ));
+            }
+        };
+        let metadata = entry.metadata().map_err(error)?;
+        if metadata.is_dir() {
+            let subdir = file_system.mkdir(dir, name);
+            scan_dir(&entry.path(), file_system, subdir)?;
+        } else if metadata.is_file() {
+            file_system.add_file(dir, name, metadata.len() as usize);
+        }
+    }
+    Ok(())
+}
+
+// A small xorshift generator, so that transcripts can be reproduced from a seed
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        Rng(seed.wrapping_mul(0x9e3779b97f4a7c15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545f4914f6cdd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn percent(&mut self, percent: u32) -> bool {
+        self.below(100) < percent as usize
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// Writes transcripts that list every directory, visiting them depth first
+struct Generator {
+    rng: Rng,
+    // Visit directories and list entries in random order instead of by name
+    shuffle: bool,
+    // Chance in percent of listing a directory again when coming back to it, and of going
+    // back through the root instead of with cd ..
+    relist: u32,
+}
+
+impl Generator {
+    fn new(seed: u64) -> Generator {
+        Generator {
+            rng: Rng::new(seed),
+            shuffle: false,
+            relist: 0,
+        }
+    }
+
+    fn transcript(&mut self, fs: &FileSystem) -> String {
+        let mut lines = vec!["$ cd /".to_owned()];
+        self.visit(fs, FileSystem::ROOT, &mut lines);
+        lines.join("\n")
+    }
+
+    fn visit(&mut self, fs: &FileSystem, dir: usize, lines: &mut Vec<String>) {
+        self.list(fs, dir, lines);
+        let mut children: Vec<(&str, usize)> = fs.children(dir).collect();
+        if self.shuffle {
+            self.rng.shuffle(&mut children);
+        }
+        for (name, d) in children {
+            lines.push(format!("$ cd {}", name));
+            self.visit(fs, d, lines);
+            if self.rng.percent(self.relist) {
+                lines.push("$ cd /".to_owned());
+                lines.extend(fs.names(dir).iter().map(|name| format!("$ cd {}", name)));
+            } else {
+                lines.push("$ cd ..".to_owned());
+            }
+            if self.rng.percent(self.relist) {
+                self.list(fs, dir, lines);
+            }
+        }
+    }
+
+    fn list(&mut self, fs: &FileSystem, dir: usize, lines: &mut Vec<String>) {
+        let mut entries: Vec<String> = fs
+            .children(dir)
+            .map(|(name, _)| format!("dir {}", name))
+            .chain(
+                fs.files(dir)
+                    .map(|(name, size)| format!("{} {}", size, name)),
+            )
+            .collect();
+        if self.shuffle {
+            self.rng.shuffle(&mut entries);
+        }
+        lines.push("$ ls".to_owned());
+        lines.extend(entries);
+    }
+}
+
+// A transcript from a file with one, a file with a tree or a directory on disk
+fn load(filename: &str) -> Result<String, String> {
+    let error = |e: std::io::Error| format!("{}: {}", filename, e);
+    if fs::metadata(filename).map_err(error)?.is_dir() {
+        return Ok(Generator::new(0).transcript(&scan(Path::new(filename))?));
+    }
+    let input = fs::read_to_string(filename).map_err(error)?;
+    if input.trim_start().starts_with("- ") {
+        let tree = parse_tree(&input).map_err(|e| format!("{}: {}", filename, e))?;
+        return Ok(Generator::new(0).transcript(&tree));
+    }
+    Ok(input)
+}
+
+fn small_dirs_size(fs: &FileSystem) -> usize {
+    fs.sizes().into_iter().filter(|size| size <= &100000).sum()
+}
+
+// Size of the directory to delete, or 0 when there is enough space already
+fn deleted_size(fs: &FileSystem, disk: &Disk) -> Result<usize, String> {
+    let sizes = fs.sizes();
+    Ok(disk.dir_to_delete(fs)?.map_or(0, |d| sizes[d]))
+}
+
+fn solve_part1(input: &str) -> usize {
+    small_dirs_size(&interpret(input).unwrap())
+}
+
+fn solve_part2(input: &str, disk: &Disk) -> usize {
+    deleted_size(&interpret(input).unwrap(), disk).unwrap()
+}
+
+fn options_from_args(args: &[String]) -> Result<(Disk, Report), String> {
+    let option = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .map(|i| args.get(i + 1).ok_or(format!("Missing value for {}", name)))
+            .transpose()
+    };
+    let number = |name: &str| -> Result<Option<usize>, String> {
+        option(name)?
+            .map(|n| {
+                n.parse::<usize>()
+                    .map_err(|_| format!("Failed to parse {} from '{}'", name, n))
+            })
+            .transpose()
+    };
+    let puzzle = Disk::puzzle();
+    let disk = Disk {
+        size: number("--disk")?.unwrap_or(puzzle.size),
+        required: number("--required")?.unwrap_or(puzzle.required),
+    };
+    let report = Report {
+        sort: option("--sort")?.map_or(Ok(SortBy::Name), |s| s.parse())?,
+        max_depth: number("--depth")?,
+        human: args.iter().any(|a| a == "--human"),
+    };
+    Ok((disk, report))
+}
+
+struct Repl {
+    file_system: FileSystem,
+    disk: Disk,
+}
+
+impl Repl {
+    fn dir(&self, args: &[&str]) -> Result<usize, String> {
+        let path = aoc_repl::arg_or(args, 0, "path", "/".to_string())?;
+        self.file_system
+            .find(&path)
+            .ok_or(format!("No directory {}", path))
+    }
+
+    fn report(&self, args: &[&str]) -> Result<Report, String> {
+        Ok(Report {
+            max_depth: Some(aoc_repl::arg_or(args, 0, "depth", usize::MAX)?),
+            ..Report::default()
+        })
+    }
+}
+
+impl aoc_repl::Introspect for Repl {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![
+            ("size", "[path]", "Total size of a directory"),
+            ("ls", "[path]", "List a directory"),
+            ("tree", "[depth]", "Print the file system as a tree"),
+            ("du", "[depth]", "Print the size of every directory"),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "size" => Ok(self.file_system.sizes()[self.dir(args)?].to_string()),
+            "ls" => {
+                let dir = self.dir(args)?;
+                let sizes = self.file_system.sizes();
+                let lines: Vec<String> = self
+                    .file_system
+                    .children(dir)
+                    .map(|(name, d)| format!("dir {} ({})", name, sizes[d]))
+                    .chain(
+                        self.file_system
+                            .files(dir)
+                            .map(|(name, size)| format!("{} {}", size, name)),
+                    )
+                    .collect();
+                Ok(lines.join("\n"))
+            }
+            "tree" => Ok(self.report(args)?.tree(&self.file_system)),
+            "du" => Ok(self.report(args)?.du(&self.file_system)),
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(small_dirs_size(&self.file_system).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(deleted_size(&self.file_system, &self.disk)?.to_string())
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
+    let filename = args.get(1).expect(
+        format!(
+            "Usage: {} input-filename|tree-filename|directory [--repl] [--disk n] [--required n] [--tree] \
+            [--du] [--sort name|size] [--depth n] [--human] \
+            [--transcript output [--seed n] [--shuffle] [--relist percent]]",
+            PACKAGE_NAME.unwrap()
+        )
+        .as_str(),
+    );
+
+    println!("Reading input from {}", filename);
+    let input = load(filename).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        process::exit(1);
+    });
+    let (disk, report) = options_from_args(&args).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        process::exit(1);
+    });
+    let file_system = interpret(&input).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        process::exit(1);
+    });
+    if let Err(e) = disk.dir_to_delete(&file_system) {
+        println!("Error: {}", e);
+        process::exit(1);
+    }
+
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl { file_system, disk };
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    if let Some(i) = args.iter().position(|a| a == "--transcript") {
+        let output = args
+            .get(i + 1)
+            .expect("Missing output filename for --transcript");
+        let seed = match args.iter().position(|a| a == "--seed") {
+            Some(i) => args
+                .get(i + 1)
+                .and_then(|n| n.parse().ok())
+                .expect("Missing seed"),
+            None => 0,
+        };
+        let mut generator = Generator::new(seed);
+        generator.shuffle = args.iter().any(|a| a == "--shuffle");
+        if let Some(i) = args.iter().position(|a| a == "--relist") {
+            generator.relist = args
+                .get(i + 1)
+                .and_then(|n| n.parse().ok())
+                .expect("Missing percent for --relist");
+        }
+        let transcript = generator.transcript(&file_system) + "\n";
+        fs::write(output, transcript).expect("Failed to write file");
+        println!("Wrote {}", output);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
+    println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
+
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input, &disk));
+    println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
+
+    if let Ok(Some(dir)) = disk.dir_to_delete(&file_system) {
+        println!("Delete {}", file_system.path(dir));
+    }
+    if args.iter().any(|a| a == "--tree") {
+        println!("{}", report.tree(&file_system));
+    }
+    if args.iter().any(|a| a == "--du") {
+        println!("{}", report.du(&file_system));
+    }
+}
+
+#[cfg(test)]
+mod tests_day7 {
+    use super::*;
+
+    const EXAMPLE1: &str = "
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    #[test]
+    fn test1_1() {
+        assert_eq!(solve_part1(EXAMPLE1), 95437);
+    }
+
+    #[test]
+    fn test2_1() {
+        assert_eq!(solve_part2(EXAMPLE1, &Disk::puzzle()), 24933642);
+    }
+
+    #[test]
+    fn test2_disk_1() {
+        let fs = interpret(EXAMPLE1).unwrap();
+        let path = |disk: Disk| disk.dir_to_delete(&fs).map(|d| d.map(|d| fs.path(d)));
+        assert_eq!(path(Disk::puzzle()), Ok(Some("/d".to_owned())));
+        let disk = Disk {
+            size: 48381165,
+            required: 90000,
+        };
+        assert_eq!(path(disk), Ok(Some("/a".to_owned())));
+        let disk = Disk {
+            size: 48381165,
+            required: 1,
+        };
+        assert_eq!(path(disk), Ok(Some("/a/e".to_owned())));
+        let disk = Disk {
+            size: 100000000,
+            required: 1000,
+        };
+        assert_eq!(path(disk), Ok(None));
+        let disk = Disk {
+            size: 1000,
+            required: 0,
+        };
+        assert!(path(disk).is_err());
+    }
+
+    #[test]
+    fn test_report_1() {
+        let fs = interpret(EXAMPLE1).unwrap();
+        assert_eq!(
+            Report::default().tree(&fs),
+            "- / (dir, size=48381165)
+  - a (dir, size=94853)
+    - e (dir, size=584)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir, size=24933642)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)"
+        );
+        let report = Report {
+            sort: SortBy::Size,
+            max_depth: Some(1),
+            human: true,
+        };
+        assert_eq!(
+            report.tree(&fs),
+            "- / (dir, size=47M)
+  - d (dir, size=24M)
+  - b.txt (file, size=15M)
+  - c.dat (file, size=8.2M)
+  - a (dir, size=93K)"
+        );
+    }
+
+    #[test]
+    fn test_report_2() {
+        let fs = interpret(EXAMPLE1).unwrap();
+        assert_eq!(
+            Report::default().du(&fs),
+            "584\t/a/e\n94853\t/a\n24933642\t/d\n48381165\t/"
+        );
+        let report = Report {
+            sort: SortBy::Size,
+            max_depth: Some(1),
+            human: false,
+        };
+        assert_eq!(report.du(&fs), "48381165\t/\n24933642\t/d\n94853\t/a");
+        assert_eq!(human_size(1023), "1023");
+        assert_eq!(human_size(1024), "1.0K");
+        assert_eq!(human_size(1025), "1.1K");
+        assert_eq!(human_size(10 * 1024 * 1024), "10M");
+    }
+
+    #[test]
+    fn test_interpret_1() {
+        let fs = interpret(EXAMPLE1).unwrap();
+        let sizes = fs.sizes();
+        let size_of = |path: &str| sizes[fs.find(path).unwrap()];
+        assert_eq!(size_of("/"), 48381165);
+        assert_eq!(size_of("/a"), 94853);
+        assert_eq!(size_of("/a/e"), 584);
+        assert_eq!(size_of("/d"), 24933642);
+        assert_eq!(fs.dir_count(), 4);
+        assert_eq!(fs.find("/a/x"), None);
+
+        let a = fs.find("/a").unwrap();
+        assert_eq!(fs.path(fs.find("a/e").unwrap()), "/a/e");
+        let children: Vec<(&str, usize)> = fs.children(a).collect();
+        assert_eq!(children, vec![("e", fs.find("/a/e").unwrap())]);
+        assert_eq!(
+            fs.files(a).collect::<Vec<_>>(),
+            vec![("f", 29116), ("g", 2557), ("h.lst", 62596)]
+        );
+    }
+
+    #[test]
+    fn test_interpret_2() {
+        // Going up from the root, back to the root and listing a directory twice
+        let fs = interpret(
+            "$ cd ..\n$ ls\n10 x\ndir a\n$ cd a\n$ ls\n5 y\n$ cd /\n$ cd a\n$ ls\n5 y\n$ cd ..\n$ ls\n10 x\ndir a",
+        )
+        .unwrap();
+        assert_eq!(fs.dir_count(), 2);
+        assert_eq!(fs.sizes(), vec![15, 5]);
+        assert!(interpret("$ cd /\nhello").is_err());
+    }
+
+    #[test]
+    fn test_parse_1() {
+        let fs = interpret(
+            "$ cd /\n$ ls\ndir Build-2.0\n$ cd Build-2.0\n$ ls\n12 lib_x.SO\n7 .hidden\n$ cd ..",
+        )
+        .unwrap();
+        let dir = fs.find("/Build-2.0").unwrap();
+        assert_eq!(
+            fs.files(dir).collect::<Vec<_>>(),
+            vec![(".hidden", 7), ("lib_x.SO", 12)]
+        );
+        assert_eq!(
+            parse("$ cd a\n\n$ ls\n1 b").unwrap(),
+            vec![
+                (1, Command::Cd(Target::Dir("a".to_owned()))),
+                (3, Command::Ls(vec![Entry::File("b".to_owned(), 1)]))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_2() {
+        assert_eq!(
+            interpret("$ cd /\n$ rm -rf a").err(),
+            Some(TranscriptError::UnknownCommand {
+                line: 2,
+                command: "rm".to_owned()
+            })
+        );
+        assert_eq!(
+            interpret("$ cd a b").err(),
+            Some(TranscriptError::BadArguments {
+                line: 1,
+                command: "cd".to_owned()
+            })
+        );
+        assert_eq!(
+            interpret("$ ls\nx y").err(),
+            Some(TranscriptError::BadEntry {
+                line: 2,
+                text: "x y".to_owned()
+            })
+        );
+        assert_eq!(
+            interpret("$ cd /\n10 a").err(),
+            Some(TranscriptError::OutputWithoutLs { line: 2 })
+        );
+        assert_eq!(
+            interpret("$ ls\ndir a\n$ cd a\n$ ls\n$ cd b").err(),
+            Some(TranscriptError::NotListed {
+                line: 5,
+                name: "b".to_owned(),
+                dir: "/a".to_owned()
+            })
+        );
+        assert_eq!(
+            TranscriptError::OutputWithoutLs { line: 2 }.to_string(),
+            "Line 2: Output without a preceding ls"
+        );
+    }
+
+    const EXAMPLE_TREE: &str = "
+- / (dir)
+  - a (dir)
+    - e (dir)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir)
+    - j (file, size=4060174)
+    - d.log (file, size=8033020)
+    - d.ext (file, size=5626152)
+    - k (file, size=7214296)";
+
+    fn random_file_system(rng: &mut Rng, dir_count: usize) -> FileSystem {
+        let mut fs = FileSystem::new();
+        for i in 0..dir_count {
+            let parent = rng.below(fs.dir_count());
+            fs.mkdir(parent, &format!("d{}", i));
+        }
+        for i in 0..rng.below(3 * dir_count + 1) {
+            let dir = rng.below(fs.dir_count());
+            fs.add_file(dir, &format!("f{}.txt", i), rng.below(200000));
+        }
+        fs
+    }
+
+    #[test]
+    fn test_parse_tree_1() {
+        let fs = parse_tree(EXAMPLE_TREE).unwrap();
+        let tree = Report::default().tree(&fs);
+        assert_eq!(tree, Report::default().tree(&interpret(EXAMPLE1).unwrap()));
+        assert_eq!(Report::default().tree(&parse_tree(&tree).unwrap()), tree);
+
+        assert!(parse_tree("- a (dir)").is_err());
+        assert!(parse_tree("- / (dir)\n    - a (dir)").is_err());
+        assert!(parse_tree("- / (dir)\n  - a (file)").is_err());
+        assert!(parse_tree("- / (dir)\n  - a b (dir)").is_err());
+    }
+
+    #[test]
+    fn test_generate_1() {
+        let fs = parse_tree(EXAMPLE_TREE).unwrap();
+        let transcript = Generator::new(0).transcript(&fs);
+        assert_eq!(
+            transcript,
+            "$ cd /
+$ ls
+dir a
+dir d
+14848514 b.txt
+8504156 c.dat
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+5626152 d.ext
+8033020 d.log
+4060174 j
+7214296 k
+$ cd .."
+        );
+        assert_eq!(solve_part1(&transcript), 95437);
+        assert_eq!(solve_part2(&transcript, &Disk::puzzle()), 24933642);
+    }
+
+    #[test]
+    fn test_generate_2() {
+        // Any transcript of a tree, in any order and with any detours, gives back the tree
+        let mut rng = Rng::new(7);
+        for seed in 0..50 {
+            let fs = random_file_system(&mut rng, seed % 20);
+            let mut generator = Generator::new(seed as u64);
+            generator.shuffle = seed % 2 == 0;
+            generator.relist = (seed as u32 * 7) % 60;
+            let transcript = generator.transcript(&fs);
+            let interpreted = interpret(&transcript).unwrap();
+
+            let report = Report::default();
+            assert_eq!(report.tree(&interpreted), report.tree(&fs));
+            let mut sizes = fs.sizes();
+            let mut interpreted_sizes = interpreted.sizes();
+            sizes.sort();
+            interpreted_sizes.sort();
+            assert_eq!(interpreted_sizes, sizes);
+            let small: usize = sizes.iter().filter(|&&size| size <= 100000).sum();
+            assert_eq!(solve_part1(&transcript), small);
+        }
+    }
+
+    #[test]
+    fn test_scan_1() {
+        let root = env::temp_dir().join(format!("aoc2022-day7-scan-{}", process::id()));
+        fs::create_dir_all(root.join("a/e")).unwrap();
+        fs::write(root.join("b.txt"), "hello").unwrap();
+        fs::write(root.join("a/e/i"), "abc").unwrap();
+        let scanned = scan(&root);
+        fs::write(root.join("a/with space"), "").unwrap();
+        let with_space = scan(&root);
+        fs::remove_dir_all(&root).unwrap();
+
+        let file_system = scanned.unwrap();
+        assert_eq!(
+            Report::default().tree(&file_system),
+            "- / (dir, size=8)
+  - a (dir, size=3)
+    - e (dir, size=3)
+      - i (file, size=3)
+  - b.txt (file, size=5)"
+        );
+        assert!(with_space.is_err());
+    }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl {
+            file_system: interpret(EXAMPLE1).unwrap(),
+            disk: Disk::puzzle(),
+        };
+        assert_eq!(repl.part1(), Ok("95437".to_string()));
+        assert_eq!(repl.part2(), Ok("24933642".to_string()));
+        assert_eq!(repl.execute("size", &["/a"]), Ok("94853".to_string()));
+        assert_eq!(
+            repl.execute("ls", &["/a"]),
+            Ok("dir e (584)\n29116 f\n2557 g\n62596 h.lst".to_string())
+        );
+        assert_eq!(
+            repl.execute("du", &["1"]),
+            Ok("94853\t/a\n24933642\t/d\n48381165\t/".to_string())
+        );
+        assert!(repl.execute("size", &["/x"]).is_err());
+    }
+}
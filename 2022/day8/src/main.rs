@@ -60,8 +60,7 @@ impl Forrest {
     }
 }
 
-fn solve_part1(input: &str) -> usize {
-    let forrest = Forrest::from_str(input);
+fn count_visible(forrest: &Forrest) -> usize {
     let mut visible: HashSet<usize> = HashSet::new();
 
     for y in 1..forrest.height - 1 {
@@ -188,34 +187,88 @@ fn score(forrest: &Forrest, x: usize, y: usize) -> usize {
     s
 }
 
-fn solve_part2(input: &str) -> usize {
-    let forrest = Forrest::from_str(input);
+fn max_score(forrest: &Forrest) -> usize {
     let mut m = 0;
 
     for x in 0..forrest.width {
         for y in 0..forrest.height {
-            let s = score(&forrest, x, y);
+            let s = score(forrest, x, y);
             m = s.max(m);
         }
     }
     m
 }
 
+fn solve_part1(input: &str) -> usize {
+    count_visible(&Forrest::from_str(input))
+}
+
+fn solve_part2(input: &str) -> usize {
+    max_score(&Forrest::from_str(input))
+}
+
+struct Repl {
+    forrest: Forrest,
+}
+
+impl Repl {
+    fn new(input: &str) -> Repl {
+        Repl {
+            forrest: Forrest::from_str(input),
+        }
+    }
+}
+
+impl aoc_repl::Introspect for Repl {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![("score", "<x,y>", "Scenic score of a tree")]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "score" => {
+                let (x, y) = aoc_repl::arg_pair::<usize>(args, 0, "x,y")?;
+                if x >= self.forrest.width || y >= self.forrest.height {
+                    return Err(format!("{},{} is outside of the forrest", x, y));
+                }
+                Ok(score(&self.forrest, x, y).to_string())
+            }
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(count_visible(&self.forrest).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(max_score(&self.forrest).to_string())
+    }
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
     const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
     let filename = args
         .get(1)
-        .expect(format!("Usage: {} input-filename", PACKAGE_NAME.unwrap()).as_str());
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
 
     println!("Reading input from {}", filename);
     let input = fs::read_to_string(filename).expect("Failed to read file");
 
-    let answer1 = solve_part1(&input);
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
     println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
 
-    let answer2 = solve_part2(&input);
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
     println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
 }
 
 #[cfg(test)]
@@ -260,4 +313,14 @@ mod tests_day8 {
         let forrest = Forrest::from_str(EXAMPLE1);
         assert_eq!(score(&forrest, 0, 2), 2);
     }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert_eq!(repl.execute("score", &["2,1"]), Ok("4".to_string()));
+        assert!(repl.execute("score", &["5,0"]).is_err());
+        assert_eq!(repl.part1(), Ok(solve_part1(EXAMPLE1).to_string()));
+        assert_eq!(repl.part2(), Ok(solve_part2(EXAMPLE1).to_string()));
+    }
 }
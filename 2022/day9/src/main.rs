@@ -105,45 +105,86 @@ fn read_moves(input: &str) -> Vec<(Vector2, &str)> {
         .collect()
 }
 
-fn solve_part1(input: &str) -> usize {
-    let moves = read_moves(input);
-    let mut head = Vector2::new(0, 0);
-    let mut tail = Vector2::new(0, 0);
+// Positions visited by the last knot of a rope
+fn count_tail_positions(moves: &[(Vector2, &str)], knot_count: usize) -> usize {
+    assert!(knot_count > 0);
+    let tail_index = knot_count - 1;
+    let mut worm: Vec<Vector2> = (0..knot_count).map(|_| Vector2::new(0, 0)).collect();
     let mut visited: HashSet<Vector2> = HashSet::new();
-    visited.insert(tail.clone());
+    visited.insert(worm[tail_index].clone());
 
     for (m, _) in moves {
         let m_step = m.sign();
+        let step_count = m.manhattan_distance();
+
+        for _ in 0..step_count {
+            worm[0] = worm[0].add(&m_step);
 
-        for _ in 0..m.abs().max_element() {
-            head = head.add(&m_step);
-            tail = tail.follow(&head);
-            visited.insert(tail.clone());
+            for i in 1..knot_count {
+                worm[i] = worm[i].follow(&worm[i - 1]);
+            }
+            visited.insert(worm[tail_index].clone());
         }
     }
     visited.len()
 }
 
+fn solve_part1(input: &str) -> usize {
+    count_tail_positions(&read_moves(input), 2)
+}
+
 fn solve_part2(input: &str) -> usize {
-    let moves = read_moves(input);
-    let mut worm: Vec<Vector2> = (0..10).map(|_| Vector2::new(0, 0)).collect();
-    let mut visited: HashSet<Vector2> = HashSet::new();
-    visited.insert(worm[9].clone());
+    count_tail_positions(&read_moves(input), 10)
+}
 
-    for (m, _) in moves {
-        let m_step = m.sign();
-        let step_count = m.manhattan_distance();
+struct Repl<'a> {
+    moves: Vec<(Vector2, &'a str)>,
+}
 
-        for _ in 0..step_count {
-            worm[0] = worm[0].add(&m_step);
+impl<'a> Repl<'a> {
+    fn new(input: &'a str) -> Repl<'a> {
+        Repl {
+            moves: read_moves(input),
+        }
+    }
+}
 
-            for i in 1..10 {
-                worm[i] = worm[i].follow(&worm[i - 1]);
+impl<'a> aoc_repl::Introspect for Repl<'a> {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![
+            ("moves", "", "Number of moves and of steps in them"),
+            (
+                "knots",
+                "<n>",
+                "Positions visited by the tail of a rope with n knots",
+            ),
+        ]
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "moves" => {
+                let steps: isize = self.moves.iter().map(|(m, _)| m.manhattan_distance()).sum();
+                Ok(format!("{} moves, {} steps", self.moves.len(), steps))
+            }
+            "knots" => {
+                let n: usize = aoc_repl::arg(args, 0, "n")?;
+                if n == 0 {
+                    return Err("A rope has at least one knot".to_string());
+                }
+                Ok(count_tail_positions(&self.moves, n).to_string())
             }
-            visited.insert(worm[9].clone());
+            _ => Err(format!("Unhandled command {}", command)),
         }
     }
-    visited.len()
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(count_tail_positions(&self.moves, 2).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(count_tail_positions(&self.moves, 10).to_string())
+    }
 }
 
 fn main() {
@@ -151,16 +192,24 @@ fn main() {
     const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
     let filename = args
         .get(1)
-        .expect(format!("Usage: {} input-filename", PACKAGE_NAME.unwrap()).as_str());
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
 
     println!("Reading input from {}", filename);
     let input = fs::read_to_string(filename).expect("Failed to read file");
 
-    let answer1 = solve_part1(&input);
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
     println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
 
-    let answer2 = solve_part2(&input);
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
     println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
 }
 
 #[cfg(test)]
@@ -201,4 +250,18 @@ U 20";
     fn test2_2() {
         assert_eq!(solve_part2(EXAMPLE2), 36);
     }
+
+    #[test]
+    fn test_repl_1() {
+        use aoc_repl::Introspect;
+        let mut repl = Repl::new(EXAMPLE1);
+        assert_eq!(
+            repl.execute("moves", &[]),
+            Ok("8 moves, 24 steps".to_string())
+        );
+        assert_eq!(repl.execute("knots", &["1"]), Ok("21".to_string()));
+        assert!(repl.execute("knots", &["0"]).is_err());
+        assert_eq!(repl.part1(), Ok("13".to_string()));
+        assert_eq!(repl.part2(), Ok("1".to_string()));
+    }
 }
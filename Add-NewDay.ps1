@@ -4,30 +4,46 @@ Param(
 	[int]
 	$Day,
 
+	[ValidateRange(2015, 2100)]
+	[int]
+	$Year=(Get-Date).Year,
+
 	[String]
 	$SourcePackage="dayx"
 )
 $TargetPackage = "day$Day"
-$TargetToml = "$TargetPackage/Cargo.toml"
-$TargetMain= "$TargetPackage/src/main.rs"
+$TargetDir = "$Year/$TargetPackage"
+$TargetToml = "$TargetDir/Cargo.toml"
+$TargetMain= "$TargetDir/src/main.rs"
 $WorkspaceToml = "Cargo.toml"
 $TempFile = ".tempFile"
-$CommitMsg ="$Day`: Add Skeleton"
+$CommitMsg ="$Year/$Day`: Add Skeleton"
+
+If (Test-Path -Path "$TargetDir") {
+	Write-Error "$TargetDir already exists."
+	Exit 1
+}
 
-Copy-Item -Recurse $SourcePackage -Destination $TargetPackage
+New-Item -ItemType Directory -Force -Path "$Year" | Out-Null
+Copy-Item -Recurse $SourcePackage -Destination $TargetDir
 
-(Get-Content -Path $TargetToml ) -Replace $SourcePackage, $TargetPackage | Add-Content -Path $TempFile
+# The template uses dayx and yyyy as placeholders for day and year
+(Get-Content -Path $TargetToml ) -Replace $SourcePackage, $TargetPackage -Replace "yyyy", $Year | Add-Content -Path $TempFile
 Remove-Item $TargetToml
 Move-Item -Force -Path $TempFile -Destination $TargetToml
 
 (Get-Content -Path $TargetMain ) -Replace $SourcePackage, $TargetPackage | Add-Content -Path $TempFile
 Move-Item -Force -Path $TempFile -Destination $TargetMain
 
-$Search="`"$SourcePackage`""
-$Replace ="`"$SourcePackage`",`r`n  `"$TargetPackage`""
-(Get-Content -Path $WorkspaceToml) -Replace $Search, $Replace | Add-Content -Path $TempFile
-Move-Item -Force -Path $TempFile -Destination $WorkspaceToml
+# Each year is a glob in the workspace members, only add it for the first day of a year
+$YearMember="`"$Year/*`""
+If (-Not (Select-String -Path $WorkspaceToml -SimpleMatch -Quiet -Pattern $YearMember)) {
+	$Search="`"$SourcePackage`","
+	$Replace ="`"$SourcePackage`",`r`n  $YearMember,"
+	(Get-Content -Path $WorkspaceToml) -Replace $Search, $Replace | Add-Content -Path $TempFile
+	Move-Item -Force -Path $TempFile -Destination $WorkspaceToml
+}
 
-git add $TargetPackage
+git add $TargetDir
 git add $WorkspaceToml
 git commit -m $CommitMsg
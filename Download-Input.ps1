@@ -11,6 +11,10 @@ Param(
 	[int]
 	$Day,
 
+	[ValidateRange(2015, 2100)]
+	[int]
+	$Year=(Get-Date).Year,
+
 	[String]
 	$Filename="input.txt",
 
@@ -21,18 +25,18 @@ Param(
 	$Force=$false
 
 )
-$OutputDir="day$Day"
+$OutputDir="$Year/day$Day"
 $OutputPath="$OutputDir/$Filename"
 $UserAgent="Download-Input.ps1 by github.com/derwiath via cURL"
-$URL="https://adventofcode.com/2022/day/$Day/input"
-$CommitMsg ="$Day`: Add Input"
+$URL="https://adventofcode.com/$Year/day/$Day/input"
+$CommitMsg ="$Year/$Day`: Add Input"
 
 If (-Not (Test-Path -Path "$CookieFilename" -PathType Leaf)) {
 	Write-Error "Failed to find $CookieFilename"
 	Exit 1
 }
 ElseIf (-Not (Test-Path -Path "$OutputDir" -PathType Container)) {
-	Write-Error "Failed to find $OutputDir."
+	Write-Error "Failed to find $OutputDir. Use Add-NewDay.ps1 -Year $Year -Day $Day to create it."
 	Exit 1
 }
 ElseIf ((-Not $Force) -And (Test-Path -Path "$OutputPath" -PathType Leaf)) {
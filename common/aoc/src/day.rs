@@ -0,0 +1,115 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub root: PathBuf,
+}
+
+impl Day {
+    pub fn find(root: &Path, spec: &str) -> Result<Day, String> {
+        let (year, day) = parse_spec(spec).ok_or(format!("Failed to parse day '{}'", spec))?;
+        let year = match year {
+            Some(year) => year,
+            None => latest_year(root).ok_or("Failed to find any year directory")?,
+        };
+        let day = Day {
+            year,
+            day,
+            root: root.to_path_buf(),
+        };
+        if !day.dir().is_dir() {
+            return Err(format!("Failed to find {}", day.dir().display()));
+        }
+        Ok(day)
+    }
+
+    pub fn package(&self) -> String {
+        format!("aoc{}-day{}", self.year, self.day)
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        self.root
+            .join(self.year.to_string())
+            .join(format!("day{}", self.day))
+    }
+
+    pub fn input(&self) -> PathBuf {
+        self.dir().join("input.txt")
+    }
+}
+
+// Accepts 17, day17, 2022/day17 and 2022/17
+fn parse_spec(spec: &str) -> Option<(Option<u32>, u32)> {
+    let (year, day) = match spec.trim_end_matches('/').split_once('/') {
+        Some((year, day)) => (Some(year.parse::<u32>().ok()?), day),
+        None => (None, spec),
+    };
+    let day = day.strip_prefix("day").unwrap_or(day).parse::<u32>().ok()?;
+    if (1..=25).contains(&day) {
+        Some((year, day))
+    } else {
+        None
+    }
+}
+
+fn latest_year(root: &Path) -> Option<u32> {
+    fs::read_dir(root)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+        .max()
+}
+
+pub fn workspace_root() -> Result<PathBuf, String> {
+    let current = env::current_dir().map_err(|e| e.to_string())?;
+    current
+        .ancestors()
+        .find(|dir| {
+            fs::read_to_string(dir.join("Cargo.toml"))
+                .map(|toml| toml.contains("[workspace]"))
+                .unwrap_or(false)
+        })
+        .map(|dir| dir.to_path_buf())
+        .ok_or(format!(
+            "Failed to find workspace root from {}",
+            current.display()
+        ))
+}
+
+#[cfg(test)]
+mod tests_day {
+    use super::*;
+
+    #[test]
+    fn test_parse_spec_1() {
+        assert_eq!(parse_spec("17"), Some((None, 17)));
+        assert_eq!(parse_spec("day17"), Some((None, 17)));
+        assert_eq!(parse_spec("2022/day17"), Some((Some(2022), 17)));
+        assert_eq!(parse_spec("2022/day17/"), Some((Some(2022), 17)));
+        assert_eq!(parse_spec("2022/5"), Some((Some(2022), 5)));
+    }
+
+    #[test]
+    fn test_parse_spec_2() {
+        assert_eq!(parse_spec("dayx"), None);
+        assert_eq!(parse_spec("26"), None);
+        assert_eq!(parse_spec("0"), None);
+        assert_eq!(parse_spec("x/day1"), None);
+    }
+
+    #[test]
+    fn test_package_1() {
+        let day = Day {
+            year: 2022,
+            day: 7,
+            root: PathBuf::from("aoc"),
+        };
+        assert_eq!(day.package(), "aoc2022-day7");
+        assert_eq!(day.dir(), Path::new("aoc").join("2022").join("day7"));
+    }
+}
@@ -0,0 +1,36 @@
+mod day;
+mod repl;
+mod watch;
+
+use day::Day;
+use std::env;
+use std::process;
+
+const USAGE: &str = "Usage: aoc <command> <day>
+
+Commands:
+  watch <day>  Rerun tests and solver of a day when any of its files change
+  repl <day>   Explore the parsed input of a day
+
+A day is given as 17, day17 or 2022/day17, the latest year is used when omitted.";
+
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (command, spec) = match (args.first(), args.get(1)) {
+        (Some(command), Some(spec)) => (command.as_str(), spec.as_str()),
+        _ => exit_with_error(USAGE),
+    };
+
+    let root = day::workspace_root().unwrap_or_else(|e| exit_with_error(&e));
+    let day = Day::find(&root, spec).unwrap_or_else(|e| exit_with_error(&e));
+    match command {
+        "watch" => watch::run(&day),
+        "repl" => repl::run(&day).unwrap_or_else(|e| exit_with_error(&e)),
+        _ => exit_with_error(USAGE),
+    }
+}
@@ -0,0 +1,23 @@
+use crate::day::Day;
+use std::process::Command;
+
+pub fn run(day: &Day) -> Result<(), String> {
+    let package = day.package();
+    let input = day.input();
+    if !input.is_file() {
+        return Err(format!("Failed to find {}", input.display()));
+    }
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .current_dir(&day.root)
+        .args(["run", "--release", "-q", "-p", &package, "--"])
+        .arg(&input)
+        .arg("--repl")
+        .status()
+        .map_err(|e| format!("Failed to run cargo: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", package, status))
+    }
+}
@@ -0,0 +1,265 @@
+use crate::day::Day;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct TestSummary {
+    passed: usize,
+    failed: usize,
+    failures: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Answer {
+    part: String,
+    value: String,
+    stats: Option<String>,
+}
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+fn snapshot(dir: &Path, files: &mut Snapshot) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            snapshot(&path, files);
+        } else if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+            files.insert(path, modified);
+        }
+    }
+}
+
+fn cargo(root: &Path, args: &[&str]) -> (bool, String) {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    match Command::new(cargo).current_dir(root).args(args).output() {
+        Ok(output) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            (output.status.success(), text)
+        }
+        Err(e) => (false, format!("error: Failed to run cargo: {}", e)),
+    }
+}
+
+fn parse_test_summary(output: &str) -> Option<TestSummary> {
+    let mut summary: Option<TestSummary> = None;
+    for line in output.lines() {
+        if let Some(result) = line.strip_prefix("test result: ") {
+            let s = summary.get_or_insert_with(TestSummary::default);
+            for field in result.split(';').map(|f| f.trim()) {
+                let field = field
+                    .trim_start_matches("ok. ")
+                    .trim_start_matches("FAILED. ");
+                if let Some((count, what)) = field.split_once(' ') {
+                    let count = count.parse::<usize>().unwrap_or(0);
+                    match what {
+                        "passed" => s.passed += count,
+                        "failed" => s.failed += count,
+                        _ => {}
+                    }
+                }
+            }
+        } else if let Some(test) = line
+            .strip_prefix("test ")
+            .and_then(|l| l.strip_suffix(" ... FAILED"))
+        {
+            summary
+                .get_or_insert_with(TestSummary::default)
+                .failures
+                .push(test.to_string());
+        }
+    }
+    summary
+}
+
+// Days print "Answer N: value", possibly followed by more lines, and then "Stats N: stats"
+fn parse_answers(output: &str) -> Vec<Answer> {
+    let mut answers: Vec<Answer> = Vec::new();
+    let mut in_answer = false;
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("Answer ") {
+            let (part, value) = rest.split_once(':').unwrap_or((rest, ""));
+            answers.push(Answer {
+                part: part.to_string(),
+                value: value.trim().to_string(),
+                stats: None,
+            });
+            in_answer = true;
+        } else if let Some(rest) = line.strip_prefix("Stats ") {
+            if let Some((part, stats)) = rest.split_once(':') {
+                if let Some(answer) = answers.iter_mut().find(|a| a.part == part) {
+                    answer.stats = Some(stats.trim().to_string());
+                }
+            }
+            in_answer = false;
+        } else if in_answer {
+            let answer = answers.last_mut().unwrap();
+            if !answer.value.is_empty() {
+                answer.value.push('\n');
+            }
+            answer.value.push_str(line);
+        }
+    }
+    answers
+}
+
+fn print_errors(output: &str) {
+    output
+        .lines()
+        .skip_while(|l| !l.starts_with("error"))
+        .take(20)
+        .for_each(|l| println!("  {}", l));
+}
+
+fn print_answers(answers: &[Answer], previous: &[Answer]) {
+    for answer in answers.iter() {
+        let change = match previous.iter().find(|a| a.part == answer.part) {
+            Some(prev) if prev.value == answer.value => "unchanged".to_string(),
+            Some(prev) => format!("was {}", prev.value.replace('\n', " ")),
+            None => "new".to_string(),
+        };
+        let stats = answer.stats.as_deref().unwrap_or("");
+        if answer.value.contains('\n') {
+            println!("  Answer {}: ({}) {}", answer.part, change, stats);
+            answer.value.lines().for_each(|l| println!("    {}", l));
+        } else {
+            println!(
+                "  Answer {}: {} ({}) {}",
+                answer.part, answer.value, change, stats
+            );
+        }
+    }
+}
+
+fn run_once(day: &Day, previous: &[Answer]) -> Option<Vec<Answer>> {
+    let package = day.package();
+    let (ok, output) = cargo(&day.root, &["test", "-p", &package]);
+    match parse_test_summary(&output) {
+        None => {
+            println!("{}: build failed", package);
+            print_errors(&output);
+            return None;
+        }
+        Some(summary) if !ok || summary.failed > 0 => {
+            println!(
+                "{}: tests {} passed, {} failed {}",
+                package,
+                summary.passed,
+                summary.failed,
+                summary.failures.join(" ")
+            );
+            return None;
+        }
+        Some(summary) => println!("{}: tests {} passed", package, summary.passed),
+    }
+
+    let input = day.input();
+    if !input.is_file() {
+        println!("  No {}", input.display());
+        return None;
+    }
+    let input = input.to_string_lossy().to_string();
+    let (ok, output) = cargo(
+        &day.root,
+        &["run", "--release", "-q", "-p", &package, "--", &input],
+    );
+    if !ok {
+        println!("  Solver failed");
+        print_errors(&output);
+        output
+            .lines()
+            .filter(|l| l.contains("panicked"))
+            .for_each(|l| println!("  {}", l));
+        return None;
+    }
+    let answers = parse_answers(&output);
+    print_answers(&answers, previous);
+    Some(answers)
+}
+
+pub fn run(day: &Day) -> ! {
+    println!("Watching {}", day.dir().display());
+    let mut files = Snapshot::new();
+    let mut previous: Vec<Answer> = Vec::new();
+    loop {
+        let mut current = Snapshot::new();
+        snapshot(&day.dir(), &mut current);
+        if current != files {
+            files = current;
+            println!();
+            if let Some(answers) = run_once(day, &previous) {
+                previous = answers;
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests_watch {
+    use super::*;
+
+    const TEST_OUTPUT: &str = "
+running 3 tests
+test tests_day16::test1_1 ... FAILED
+test tests_day16::test_valve_1 ... ok
+test tests_day16::test2_1 ... ok
+
+test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.03s
+";
+
+    #[test]
+    fn test_parse_test_summary_1() {
+        assert_eq!(
+            parse_test_summary(TEST_OUTPUT),
+            Some(TestSummary {
+                passed: 2,
+                failed: 1,
+                failures: vec!["tests_day16::test1_1".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_test_summary_2() {
+        assert_eq!(parse_test_summary("error[E0425]: cannot find value"), None);
+    }
+
+    const RUN_OUTPUT: &str = "Reading input from input.txt
+Answer 1: 13140
+Stats 1: 10.000µs
+Answer 2
+##..##..
+#..#..##
+Stats 2: 12.000µs
+";
+
+    #[test]
+    fn test_parse_answers_1() {
+        assert_eq!(
+            parse_answers(RUN_OUTPUT),
+            vec![
+                Answer {
+                    part: "1".to_string(),
+                    value: "13140".to_string(),
+                    stats: Some("10.000µs".to_string()),
+                },
+                Answer {
+                    part: "2".to_string(),
+                    value: "##..##..\n#..#..##".to_string(),
+                    stats: Some("12.000µs".to_string()),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,565 @@
+//! Graphs and the shortest path searches that puzzles keep asking for.
+//!
+//! A day implements `Graph` for its own map, or builds an `AdjacencyGraph` of numbered nodes
+//! or a `GridGraph` over a `Grid`, and then searches it with `bfs`, `dijkstra` or `astar`.
+//! `AllPairs` holds the distances between every two nodes of an `AdjacencyGraph`.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+pub trait Graph {
+    type Node: Copy + Eq + Hash;
+
+    // Nodes reachable in one step from node, with the cost of taking that step
+    fn neighbours(&self, node: Self::Node) -> Vec<(Self::Node, usize)>;
+}
+
+// Nodes numbered 0..node_count with directed, weighted edges
+#[derive(Debug, Clone, Default)]
+pub struct AdjacencyGraph {
+    edges: Vec<Vec<(usize, usize)>>,
+}
+
+impl AdjacencyGraph {
+    pub fn new(node_count: usize) -> AdjacencyGraph {
+        AdjacencyGraph {
+            edges: vec![Vec::new(); node_count],
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, cost: usize) {
+        self.edges[from].push((to, cost));
+    }
+
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize, cost: usize) {
+        self.add_edge(a, b, cost);
+        self.add_edge(b, a, cost);
+    }
+
+    pub fn edges(&self, node: usize) -> &[(usize, usize)] {
+        &self.edges[node]
+    }
+}
+
+impl Graph for AdjacencyGraph {
+    type Node = usize;
+
+    fn neighbours(&self, node: usize) -> Vec<(usize, usize)> {
+        self.edges[node].clone()
+    }
+}
+
+// Cells stored row by row, positions are (x, y) with y growing downwards
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "Grid size does not match cells"
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (isize, isize)) -> bool {
+        0 <= x && (x as usize) < self.width && 0 <= y && (y as usize) < self.height
+    }
+
+    pub fn get(&self, pos: (isize, isize)) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.1 as usize * self.width + pos.0 as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (isize, isize)> + '_ {
+        (0..self.height).flat_map(|y| (0..self.width).map(move |x| (x as isize, y as isize)))
+    }
+}
+
+const STRAIGHT: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const DIAGONAL: [(isize, isize); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+// A grid where step decides if, and at what cost, one cell can be left for an adjacent one
+pub struct GridGraph<'a, T, F> {
+    grid: &'a Grid<T>,
+    step: F,
+    diagonals: bool,
+}
+
+impl<'a, T, F: Fn(&T, &T) -> Option<usize>> GridGraph<'a, T, F> {
+    pub fn new(grid: &'a Grid<T>, step: F) -> GridGraph<'a, T, F> {
+        GridGraph {
+            grid,
+            step,
+            diagonals: false,
+        }
+    }
+
+    pub fn with_diagonals(mut self) -> GridGraph<'a, T, F> {
+        self.diagonals = true;
+        self
+    }
+}
+
+impl<'a, T, F: Fn(&T, &T) -> Option<usize>> Graph for GridGraph<'a, T, F> {
+    type Node = (isize, isize);
+
+    fn neighbours(&self, (x, y): (isize, isize)) -> Vec<((isize, isize), usize)> {
+        let from = match self.grid.get((x, y)) {
+            Some(from) => from,
+            None => return Vec::new(),
+        };
+        let diagonals: &[(isize, isize)] = if self.diagonals { &DIAGONAL } else { &[] };
+        STRAIGHT
+            .iter()
+            .chain(diagonals.iter())
+            .filter_map(|(dx, dy)| {
+                let to = (x + dx, y + dy);
+                let cost = (self.step)(from, self.grid.get(to)?)?;
+                Some((to, cost))
+            })
+            .collect()
+    }
+}
+
+// Admissible heuristics for A* on grids with unit steps, without and with diagonals
+pub fn manhattan(a: (isize, isize), b: (isize, isize)) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+pub fn chebyshev(a: (isize, isize), b: (isize, isize)) -> usize {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub nodes: Vec<N>,
+    pub cost: usize,
+}
+
+// Cost of reaching every node found by a search, and the node it was reached from
+#[derive(Debug, Clone)]
+pub struct SearchTree<N> {
+    start: N,
+    reached: HashMap<N, (usize, N)>,
+}
+
+impl<N: Copy + Eq + Hash> SearchTree<N> {
+    fn new(start: N) -> SearchTree<N> {
+        let mut reached = HashMap::new();
+        reached.insert(start, (0, start));
+        SearchTree { start, reached }
+    }
+
+    pub fn start(&self) -> N {
+        self.start
+    }
+
+    pub fn cost(&self, node: N) -> Option<usize> {
+        self.reached.get(&node).map(|(cost, _)| *cost)
+    }
+
+    pub fn parent(&self, node: N) -> Option<N> {
+        match self.reached.get(&node) {
+            Some((_, parent)) if node != self.start => Some(*parent),
+            _ => None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.reached.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reached.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (N, usize)> + '_ {
+        self.reached.iter().map(|(node, (cost, _))| (*node, *cost))
+    }
+
+    // Walks the parents back from node, the path starts with the start node
+    pub fn path_to(&self, node: N) -> Option<Path<N>> {
+        let cost = self.cost(node)?;
+        let mut nodes = vec![node];
+        let mut current = node;
+        while let Some(parent) = self.parent(current) {
+            nodes.push(parent);
+            current = parent;
+        }
+        nodes.reverse();
+        Some(Path { nodes, cost })
+    }
+}
+
+// Fewest steps to every reachable node, edge costs are ignored
+pub fn bfs<G: Graph>(graph: &G, start: G::Node) -> SearchTree<G::Node> {
+    let mut tree = SearchTree::new(start);
+    let mut queue: VecDeque<G::Node> = VecDeque::new();
+    queue.push_back(start);
+    while let Some(node) = queue.pop_front() {
+        let steps = tree.reached[&node].0 + 1;
+        for (n, _) in graph.neighbours(node) {
+            tree.reached.entry(n).or_insert_with(|| {
+                queue.push_back(n);
+                (steps, node)
+            });
+        }
+    }
+    tree
+}
+
+// Lowest cost to every reachable node
+pub fn dijkstra<G: Graph>(graph: &G, start: G::Node) -> SearchTree<G::Node> {
+    let mut tree = SearchTree::new(start);
+    search(graph, &mut tree, None, |_| 0);
+    tree
+}
+
+// Lowest cost path from start to goal, heuristic must never overestimate the remaining cost
+pub fn astar<G: Graph, H: Fn(G::Node) -> usize>(
+    graph: &G,
+    start: G::Node,
+    goal: G::Node,
+    heuristic: H,
+) -> Option<Path<G::Node>> {
+    let mut tree = SearchTree::new(start);
+    if search(graph, &mut tree, Some(goal), heuristic) {
+        tree.path_to(goal)
+    } else {
+        None
+    }
+}
+
+// Best first search shared by dijkstra and astar, returns true when goal is reached
+fn search<G: Graph, H: Fn(G::Node) -> usize>(
+    graph: &G,
+    tree: &mut SearchTree<G::Node>,
+    goal: Option<G::Node>,
+    heuristic: H,
+) -> bool {
+    // The heap refers to nodes by their index in pushed, so that nodes need not be Ord
+    let mut pushed: Vec<G::Node> = vec![tree.start];
+    let mut open: BinaryHeap<Reverse<(usize, usize, usize)>> = BinaryHeap::new();
+    open.push(Reverse((heuristic(tree.start), 0, 0)));
+    while let Some(Reverse((_, cost, index))) = open.pop() {
+        let node = pushed[index];
+        if cost > tree.reached[&node].0 {
+            continue;
+        }
+        if Some(node) == goal {
+            return true;
+        }
+        for (n, step) in graph.neighbours(node) {
+            let n_cost = cost + step;
+            if tree.cost(n).is_none_or(|c| n_cost < c) {
+                tree.reached.insert(n, (n_cost, node));
+                pushed.push(n);
+                open.push(Reverse((n_cost + heuristic(n), n_cost, pushed.len() - 1)));
+            }
+        }
+    }
+    false
+}
+
+// Groups of nodes that can all reach each other, a group comes before any group that reaches it
+pub fn strongly_connected_components<G: Graph, I: IntoIterator<Item = G::Node>>(
+    graph: &G,
+    nodes: I,
+) -> Vec<Vec<G::Node>> {
+    // Tarjan's algorithm, with an explicit call stack of nodes and their unvisited neighbours
+    let mut order: HashMap<G::Node, (usize, usize)> = HashMap::new();
+    let mut stack: Vec<G::Node> = Vec::new();
+    let mut on_stack: HashSet<G::Node> = HashSet::new();
+    let mut calls: Vec<(G::Node, Vec<G::Node>)> = Vec::new();
+    let mut components: Vec<Vec<G::Node>> = Vec::new();
+    let visit = |node: G::Node,
+                 order: &mut HashMap<G::Node, (usize, usize)>,
+                 calls: &mut Vec<(G::Node, Vec<G::Node>)>,
+                 stack: &mut Vec<G::Node>,
+                 on_stack: &mut HashSet<G::Node>| {
+        let index = order.len();
+        order.insert(node, (index, index));
+        stack.push(node);
+        on_stack.insert(node);
+        let mut neighbours: Vec<G::Node> = graph.neighbours(node).iter().map(|n| n.0).collect();
+        neighbours.reverse();
+        calls.push((node, neighbours));
+    };
+    for root in nodes {
+        if order.contains_key(&root) {
+            continue;
+        }
+        visit(root, &mut order, &mut calls, &mut stack, &mut on_stack);
+        while let Some((node, neighbours)) = calls.last_mut() {
+            let node = *node;
+            if let Some(n) = neighbours.pop() {
+                match order.get(&n) {
+                    None => visit(n, &mut order, &mut calls, &mut stack, &mut on_stack),
+                    Some((n_index, _)) if on_stack.contains(&n) => {
+                        let n_index = *n_index;
+                        let low = &mut order.get_mut(&node).unwrap().1;
+                        *low = (*low).min(n_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+            calls.pop();
+            let (index, low) = order[&node];
+            if let Some((parent, _)) = calls.last() {
+                let parent_low = &mut order.get_mut(parent).unwrap().1;
+                *parent_low = (*parent_low).min(low);
+            }
+            if index == low {
+                let mut component: Vec<G::Node> = Vec::new();
+                while let Some(n) = stack.pop() {
+                    on_stack.remove(&n);
+                    component.push(n);
+                    if n == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+// Distance between every two nodes of an AdjacencyGraph, and the next hop to take on the way
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllPairs {
+    distances: Vec<Vec<Option<usize>>>,
+    next: Vec<Vec<Option<usize>>>,
+}
+
+impl AllPairs {
+    pub fn floyd_warshall(graph: &AdjacencyGraph) -> AllPairs {
+        let count = graph.node_count();
+        let mut distances = vec![vec![None; count]; count];
+        let mut next = vec![vec![None; count]; count];
+        for from in 0..count {
+            distances[from][from] = Some(0);
+            next[from][from] = Some(from);
+            for (to, cost) in graph.edges(from).iter() {
+                if distances[from][*to].is_none_or(|d| *cost < d) {
+                    distances[from][*to] = Some(*cost);
+                    next[from][*to] = Some(*to);
+                }
+            }
+        }
+        for via in 0..count {
+            for from in 0..count {
+                let to_via = match distances[from][via] {
+                    Some(d) => d,
+                    None => continue,
+                };
+                for to in 0..count {
+                    if let Some(from_via) = distances[via][to] {
+                        let d = to_via + from_via;
+                        if distances[from][to].is_none_or(|current| d < current) {
+                            distances[from][to] = Some(d);
+                            next[from][to] = next[from][via];
+                        }
+                    }
+                }
+            }
+        }
+        AllPairs { distances, next }
+    }
+
+    // One breadth first search per node, distances are steps and edge costs are ignored
+    pub fn repeated_bfs(graph: &AdjacencyGraph) -> AllPairs {
+        let count = graph.node_count();
+        let mut distances = vec![vec![None; count]; count];
+        let mut next = vec![vec![None; count]; count];
+        for from in 0..count {
+            let tree = bfs(graph, from);
+            for to in 0..count {
+                if let Some(path) = tree.path_to(to) {
+                    distances[from][to] = Some(path.cost);
+                    next[from][to] = path.nodes.get(1).or(path.nodes.first()).copied();
+                }
+            }
+        }
+        AllPairs { distances, next }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.distances.len()
+    }
+
+    pub fn distance(&self, from: usize, to: usize) -> Option<usize> {
+        self.distances[from][to]
+    }
+
+    // Nodes visited going from one node to another, including both
+    pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let mut nodes = vec![from];
+        let mut current = from;
+        while current != to {
+            current = self.next[current][to]?;
+            nodes.push(current);
+        }
+        Some(nodes)
+    }
+}
+
+#[cfg(test)]
+mod tests_graph {
+    use super::*;
+
+    // 0 -> 1 -> 3 costs 2, 0 -> 2 -> 3 costs 6 but is the same number of steps, 4 is unreachable
+    fn example_graph() -> AdjacencyGraph {
+        let mut graph = AdjacencyGraph::new(5);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(2, 3, 5);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 3, 1);
+        graph.add_undirected_edge(3, 0, 10);
+        graph
+    }
+
+    const MAZE: &str = "\
+.....
+.###.
+...#.
+.#...";
+
+    fn maze() -> Grid<bool> {
+        let cells: Vec<bool> = MAZE
+            .lines()
+            .flat_map(|l| l.chars().map(|c| c == '.'))
+            .collect();
+        Grid::new(5, 4, cells)
+    }
+
+    fn open(_: &bool, to: &bool) -> Option<usize> {
+        if *to {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn test_bfs_1() {
+        let tree = bfs(&example_graph(), 0);
+        assert_eq!(tree.cost(3), Some(1));
+        assert_eq!(tree.cost(4), None);
+        assert_eq!(tree.len(), 4);
+        assert_eq!(
+            tree.path_to(1),
+            Some(Path {
+                nodes: vec![0, 1],
+                cost: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_1() {
+        let tree = dijkstra(&example_graph(), 0);
+        assert_eq!(tree.cost(3), Some(2));
+        assert_eq!(tree.cost(2), Some(1));
+        assert_eq!(tree.path_to(3).unwrap().nodes, vec![0, 1, 3]);
+        assert_eq!(tree.parent(0), None);
+        assert_eq!(tree.path_to(4), None);
+    }
+
+    #[test]
+    fn test_astar_1() {
+        let grid = maze();
+        let graph = GridGraph::new(&grid, open);
+        let goal = (2, 3);
+        let path = astar(&graph, (0, 0), goal, |p| manhattan(p, goal)).unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.nodes.first(), Some(&(0, 0)));
+        assert_eq!(path.nodes.last(), Some(&goal));
+        assert_eq!(path.nodes.len(), 6);
+        assert_eq!(astar(&graph, (0, 0), (1, 1), |_| 0), None);
+    }
+
+    #[test]
+    fn test_astar_2() {
+        let grid = maze();
+        let graph = GridGraph::new(&grid, open).with_diagonals();
+        let goal = (4, 3);
+        let path = astar(&graph, (0, 0), goal, |p| chebyshev(p, goal)).unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(dijkstra(&graph, (0, 0)).cost(goal), Some(5));
+    }
+
+    #[test]
+    fn test_grid_1() {
+        let grid = maze();
+        assert_eq!(grid.get((1, 1)), Some(&false));
+        assert_eq!(grid.get((5, 0)), None);
+        assert_eq!(grid.get((0, -1)), None);
+        assert_eq!(grid.positions().count(), 20);
+        assert_eq!(grid.positions().nth(6), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_all_pairs_1() {
+        let graph = example_graph();
+        let table = AllPairs::floyd_warshall(&graph);
+        assert_eq!(table.distance(0, 3), Some(2));
+        assert_eq!(table.distance(3, 2), Some(11));
+        assert_eq!(table.distance(0, 4), None);
+        assert_eq!(table.path(3, 2), Some(vec![3, 0, 2]));
+        assert_eq!(table.path(1, 1), Some(vec![1]));
+        assert_eq!(table.path(4, 0), None);
+    }
+
+    #[test]
+    fn test_all_pairs_2() {
+        let graph = example_graph();
+        let table = AllPairs::repeated_bfs(&graph);
+        assert_eq!(table.distance(0, 3), Some(1));
+        assert_eq!(table.distance(1, 2), Some(3));
+        assert_eq!(table.path(1, 2), Some(vec![1, 3, 0, 2]));
+        assert_eq!(table.path(2, 4), None);
+    }
+
+    #[test]
+    fn test_strongly_connected_components_1() {
+        let mut components = strongly_connected_components(&example_graph(), 0..5);
+        components.iter_mut().for_each(|c| c.sort());
+        assert_eq!(components, vec![vec![0, 1, 2, 3], vec![4]]);
+
+        let mut graph = AdjacencyGraph::new(4);
+        graph.add_edge(0, 1, 1);
+        graph.add_undirected_edge(1, 2, 1);
+        graph.add_edge(2, 3, 1);
+        let mut components = strongly_connected_components(&graph, 0..4);
+        components.iter_mut().for_each(|c| c.sort());
+        assert_eq!(components, vec![vec![3], vec![1, 2], vec![0]]);
+    }
+}
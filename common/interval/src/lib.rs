@@ -0,0 +1,414 @@
+//! Intervals of integers, and sets of them kept sorted, disjoint and merged.
+//!
+//! An `Interval` is stored half-open as `[start, end)`, use `Interval::inclusive` for the
+//! `min-max` ranges that puzzles tend to use.
+
+use std::fmt;
+use std::ops::{Add, Sub};
+
+pub trait Integer: Copy + Ord + fmt::Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+        })*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T: Integer> {
+    start: T,
+    end: T,
+}
+
+impl<T: Integer> Interval<T> {
+    // [start, end), an end before start gives an empty interval
+    pub fn new(start: T, end: T) -> Interval<T> {
+        Interval {
+            start,
+            end: end.max(start),
+        }
+    }
+
+    // [min, max], a max before min gives an empty interval. The end is stored exclusive, so
+    // max has to be below the largest value of T, which panics.
+    pub fn inclusive(min: T, max: T) -> Interval<T> {
+        if max < min {
+            Interval::new(min, min)
+        } else {
+            let end = max
+                .checked_add(T::ONE)
+                .expect("Inclusive interval can not end at the largest value of its type");
+            Interval::new(min, end)
+        }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    // Largest value in the interval
+    pub fn last(&self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.end - T::ONE)
+        }
+    }
+
+    pub fn len(&self) -> T {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn contains_interval(&self, other: &Interval<T>) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    // Overlapping or directly next to each other, so that the union is one interval
+    pub fn touches(&self, other: &Interval<T>) -> bool {
+        !self.is_empty() && !other.is_empty() && self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let i = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        if i.is_empty() {
+            None
+        } else {
+            Some(i)
+        }
+    }
+}
+
+impl<T: Integer> fmt::Debug for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:?}, {:?})", self.start, self.end)
+    }
+}
+
+// Non-empty intervals sorted by start, where no two intervals touch
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet<T: Integer> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+
+    fn normalize(mut intervals: Vec<Interval<T>>) -> IntervalSet<T> {
+        intervals.retain(|i| !i.is_empty());
+        intervals.sort_unstable_by_key(|i| (i.start, i.end));
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for i in intervals {
+            match merged.last_mut() {
+                Some(last) if last.touches(&i) => last.end = last.end.max(i.end),
+                _ => merged.push(i),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        let merged = self.intervals[first..last].iter().fold(interval, |acc, i| {
+            Interval::new(acc.start.min(i.start), acc.end.max(i.end))
+        });
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    // Number of intervals, see len for the number of values
+    pub fn interval_count(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn len(&self) -> T {
+        self.intervals.iter().fold(T::ZERO, |acc, i| acc + i.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn first(&self) -> Option<&Interval<T>> {
+        self.intervals.first()
+    }
+
+    pub fn last(&self) -> Option<&Interval<T>> {
+        self.intervals.last()
+    }
+
+    // Smallest interval containing all values of the set
+    pub fn span(&self) -> Option<Interval<T>> {
+        Some(Interval::new(self.first()?.start, self.last()?.end))
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals
+            .get(index)
+            .map(|i| i.contains(value))
+            .unwrap_or(false)
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = self.intervals.clone();
+        intervals.extend_from_slice(&other.intervals);
+        IntervalSet::normalize(intervals)
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals: Vec<Interval<T>> = Vec::new();
+        let (mut a, mut b) = (0, 0);
+        while a < self.intervals.len() && b < other.intervals.len() {
+            let (ia, ib) = (&self.intervals[a], &other.intervals[b]);
+            if let Some(i) = ia.intersection(ib) {
+                intervals.push(i);
+            }
+            if ia.end < ib.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals: Vec<Interval<T>> = Vec::new();
+        let mut b = 0;
+        for ia in self.intervals.iter() {
+            let mut start = ia.start;
+            while b < other.intervals.len() && other.intervals[b].end <= start {
+                b += 1;
+            }
+            let mut next = b;
+            while next < other.intervals.len() && other.intervals[next].start < ia.end {
+                let ib = &other.intervals[next];
+                if start < ib.start {
+                    intervals.push(Interval::new(start, ib.start));
+                }
+                start = start.max(ib.end);
+                next += 1;
+            }
+            if start < ia.end {
+                intervals.push(Interval::new(start, ia.end));
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    // Intervals between consecutive intervals of the set
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals
+            .windows(2)
+            .map(|w| Interval::new(w[0].end, w[1].start))
+    }
+}
+
+impl<T: Integer> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> IntervalSet<T> {
+        IntervalSet::normalize(vec![interval])
+    }
+}
+
+impl<T: Integer> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> IntervalSet<T> {
+        IntervalSet::normalize(iter.into_iter().collect())
+    }
+}
+
+impl<T: Integer> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|i| self.insert(i));
+    }
+}
+
+impl<'a, T: Integer> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Integer> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.intervals.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests_interval {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|(start, end)| Interval::new(*start, *end))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval_inclusive_1() {
+        assert_eq!(Interval::inclusive(2, 4), Interval::new(2, 5));
+        assert_eq!(Interval::inclusive(2, 4).len(), 3);
+        assert_eq!(Interval::inclusive(2, 4).last(), Some(4));
+        assert!(Interval::inclusive(4, 2).is_empty());
+        assert_eq!(Interval::inclusive(4, 2).last(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_interval_inclusive_2() {
+        Interval::inclusive(0u8, u8::MAX);
+    }
+
+    #[test]
+    fn test_interval_contains_1() {
+        let i = Interval::inclusive(2, 8);
+        assert!(i.contains(2));
+        assert!(i.contains(8));
+        assert!(!i.contains(9));
+        assert!(i.contains_interval(&Interval::inclusive(3, 7)));
+        assert!(i.contains_interval(&Interval::inclusive(2, 8)));
+        assert!(!i.contains_interval(&Interval::inclusive(1, 7)));
+    }
+
+    #[test]
+    fn test_interval_overlaps_1() {
+        assert!(Interval::inclusive(5, 7).overlaps(&Interval::inclusive(7, 9)));
+        assert!(!Interval::inclusive(2, 3).overlaps(&Interval::inclusive(4, 5)));
+        assert!(Interval::inclusive(2, 3).touches(&Interval::inclusive(4, 5)));
+        assert!(!Interval::new(0, 0).overlaps(&Interval::new(0, 0)));
+    }
+
+    #[test]
+    fn test_interval_intersection_1() {
+        assert_eq!(
+            Interval::inclusive(0, 2).intersection(&Interval::inclusive(1, 3)),
+            Some(Interval::inclusive(1, 2))
+        );
+        assert_eq!(
+            Interval::inclusive(0, 2).intersection(&Interval::inclusive(3, 3)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_set_normalize_1() {
+        let s = set(&[(5, 7), (0, 2), (1, 3), (3, 4), (9, 9), (10, 12)]);
+        assert_eq!(
+            s.iter().copied().collect::<Vec<_>>(),
+            vec![
+                Interval::new(0, 4),
+                Interval::new(5, 7),
+                Interval::new(10, 12)
+            ]
+        );
+        assert_eq!(s.len(), 8);
+        assert_eq!(s.interval_count(), 3);
+        assert_eq!(s.span(), Some(Interval::new(0, 12)));
+    }
+
+    #[test]
+    fn test_set_insert_1() {
+        let mut s = set(&[(0, 2), (4, 6), (8, 10)]);
+        s.insert(Interval::new(2, 4));
+        assert_eq!(s, set(&[(0, 6), (8, 10)]));
+        s.insert(Interval::new(-5, -3));
+        assert_eq!(s, set(&[(-5, -3), (0, 6), (8, 10)]));
+        s.insert(Interval::new(5, 20));
+        assert_eq!(s, set(&[(-5, -3), (0, 20)]));
+        s.insert(Interval::new(1, 1));
+        assert_eq!(s, set(&[(-5, -3), (0, 20)]));
+    }
+
+    #[test]
+    fn test_set_contains_1() {
+        let s = set(&[(0, 2), (4, 6)]);
+        assert!(s.contains(0));
+        assert!(s.contains(1));
+        assert!(!s.contains(2));
+        assert!(s.contains(5));
+        assert!(!s.contains(6));
+        assert!(!s.contains(-1));
+    }
+
+    #[test]
+    fn test_set_union_1() {
+        assert_eq!(
+            set(&[(0, 2), (6, 8)]).union(&set(&[(2, 3), (10, 11)])),
+            set(&[(0, 3), (6, 8), (10, 11)])
+        );
+    }
+
+    #[test]
+    fn test_set_intersection_1() {
+        assert_eq!(
+            set(&[(0, 5), (8, 12)]).intersection(&set(&[(3, 9), (11, 20)])),
+            set(&[(3, 5), (8, 9), (11, 12)])
+        );
+        assert_eq!(set(&[(0, 5)]).intersection(&set(&[(5, 9)])), set(&[]));
+    }
+
+    #[test]
+    fn test_set_difference_1() {
+        assert_eq!(
+            set(&[(0, 10), (20, 30)]).difference(&set(&[(2, 4), (6, 8), (9, 22), (29, 40)])),
+            set(&[(0, 2), (4, 6), (8, 9), (22, 29)])
+        );
+        assert_eq!(set(&[(0, 10)]).difference(&set(&[(-5, 15)])), set(&[]));
+        assert_eq!(set(&[(0, 10)]).difference(&set(&[])), set(&[(0, 10)]));
+    }
+
+    #[test]
+    fn test_set_gaps_1() {
+        let s = set(&[(0, 2), (4, 6), (7, 9)]);
+        assert_eq!(
+            s.gaps().collect::<Vec<_>>(),
+            vec![Interval::new(2, 4), Interval::new(6, 7)]
+        );
+        assert_eq!(set(&[(0, 2)]).gaps().count(), 0);
+    }
+}
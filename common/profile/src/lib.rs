@@ -0,0 +1,181 @@
+//! Measures how long a solver takes and, with the `counting` feature, how much it allocates.
+//!
+//! The `counting` feature installs `CountingAllocator` as the global allocator of the
+//! binary, so it should only be enabled through a `profile` feature of a day.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        CURRENT_BYTES.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "counting")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub allocated_bytes: usize,
+    // Highest number of live bytes, on top of what was live when the measurement started
+    pub peak_bytes: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Measurement {
+    pub elapsed: Duration,
+    pub alloc: Option<AllocStats>,
+}
+
+pub fn counting_enabled() -> bool {
+    cfg!(feature = "counting")
+}
+
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Measurement) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let current_bytes = CURRENT_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(current_bytes, Ordering::Relaxed);
+
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    let alloc = if counting_enabled() {
+        Some(AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+            allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated_bytes,
+            peak_bytes: PEAK_BYTES
+                .load(Ordering::Relaxed)
+                .saturating_sub(current_bytes),
+        })
+    } else {
+        None
+    };
+    (result, Measurement { elapsed, alloc })
+}
+
+struct Bytes(usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit + 1 < UNITS.len() {
+            value /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            write!(f, "{} {}", self.0, UNITS[unit])
+        } else {
+            write!(f, "{:.1} {}", value, UNITS[unit])
+        }
+    }
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            Bytes(self.allocated_bytes),
+            Bytes(self.peak_bytes)
+        )
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.3?}", self.elapsed)?;
+        if let Some(alloc) = &self.alloc {
+            write!(f, ", {}", alloc)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_profile {
+    use super::*;
+
+    #[test]
+    fn test_bytes_1() {
+        assert_eq!(Bytes(0).to_string(), "0 B");
+        assert_eq!(Bytes(1023).to_string(), "1023 B");
+        assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+        assert_eq!(Bytes(3 * 1024 * 1024).to_string(), "3.0 MiB");
+    }
+
+    #[test]
+    fn test_alloc_stats_1() {
+        let stats = AllocStats {
+            allocations: 3,
+            allocated_bytes: 2048,
+            peak_bytes: 1024,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 allocations, 2.0 KiB allocated, 1.0 KiB peak"
+        );
+    }
+
+    #[test]
+    fn test_measure_1() {
+        let (answer, measurement) = measure(|| vec![1u64; 100].iter().sum::<u64>());
+        assert_eq!(answer, 100);
+        assert_eq!(measurement.alloc.is_some(), counting_enabled());
+    }
+}
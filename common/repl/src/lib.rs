@@ -0,0 +1,199 @@
+//! A small read-eval-print loop over the parsed input of a day.
+//!
+//! A day implements `Introspect` for a struct holding its parsed input, and starts the loop
+//! with `run_stdio` when given `--repl` after the input filename.
+
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+pub trait Introspect {
+    // Name, arguments and description of each command, part1, part2, help and quit are built in
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)>;
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String>;
+
+    // Answers of the puzzle, solved from the parsed input
+    fn part1(&self) -> Result<String, String>;
+
+    fn part2(&self) -> Result<String, String>;
+}
+
+pub fn arg<T: FromStr>(args: &[&str], index: usize, name: &str) -> Result<T, String> {
+    let s = args
+        .get(index)
+        .ok_or(format!("Missing argument <{}>", name))?;
+    s.parse::<T>()
+        .map_err(|_| format!("Failed to parse <{}> from '{}'", name, s))
+}
+
+pub fn arg_or<T: FromStr>(
+    args: &[&str],
+    index: usize,
+    name: &str,
+    default: T,
+) -> Result<T, String> {
+    if args.len() > index {
+        arg(args, index, name)
+    } else {
+        Ok(default)
+    }
+}
+
+// Parses x,y as used by the puzzles for coordinates, which run keeps in one argument as long
+// as there is no whitespace around the comma
+pub fn arg_pair<T: FromStr>(args: &[&str], index: usize, name: &str) -> Result<(T, T), String> {
+    let s = args
+        .get(index)
+        .ok_or(format!("Missing argument <{}>", name))?;
+    let parsed = s
+        .split_once(',')
+        .and_then(|(x, y)| Some((x.parse::<T>().ok()?, y.parse::<T>().ok()?)));
+    parsed.ok_or(format!("Failed to parse <{}> as x,y from '{}'", name, s))
+}
+
+fn help<T: Introspect>(state: &T) -> String {
+    let mut commands = vec![("part1", "", "Solve part 1"), ("part2", "", "Solve part 2")];
+    commands.extend(state.commands());
+    commands.push(("help", "", "Show this help"));
+    commands.push(("quit", "", "Leave the repl"));
+    let width = commands
+        .iter()
+        .map(|(name, args, _)| name.len() + args.len() + 1)
+        .max()
+        .unwrap_or(0);
+    commands
+        .iter()
+        .map(|(name, args, description)| {
+            let usage = format!("{} {}", name, args);
+            format!("  {:width$}  {}", usage, description, width = width)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn run<T: Introspect, R: BufRead, W: Write>(
+    state: &mut T,
+    input: R,
+    mut output: W,
+) -> io::Result<()> {
+    let mut lines = input.lines();
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (command, args) = match words.split_first() {
+            Some((command, args)) => (*command, args),
+            None => continue,
+        };
+        let result = match command {
+            "quit" | "exit" => break,
+            "help" => Ok(help(state)),
+            "part1" => state.part1(),
+            "part2" => state.part2(),
+            _ if state.commands().iter().any(|(name, _, _)| *name == command) => {
+                state.execute(command, args)
+            }
+            _ => Err(format!("Unknown command '{}', try help", command)),
+        };
+        match result {
+            Ok(text) => writeln!(output, "{}", text)?,
+            Err(e) => writeln!(output, "Error: {}", e)?,
+        }
+    }
+    writeln!(output)?;
+    Ok(())
+}
+
+pub fn run_stdio<T: Introspect>(state: &mut T) {
+    let stdin = io::stdin();
+    run(state, stdin.lock(), io::stdout()).expect("Failed to run repl");
+}
+
+#[cfg(test)]
+mod tests_repl {
+    use super::*;
+
+    struct Counter {
+        count: usize,
+    }
+
+    impl Introspect for Counter {
+        fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+            vec![
+                ("add", "<n>", "Add n to the count"),
+                ("count", "", "Print the count"),
+            ]
+        }
+
+        fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+            match command {
+                "add" => {
+                    self.count += arg::<usize>(args, 0, "n")?;
+                    Ok(format!("{}", self.count))
+                }
+                "count" => Ok(format!("{}", self.count)),
+                _ => unreachable!(),
+            }
+        }
+
+        fn part1(&self) -> Result<String, String> {
+            Ok(format!("{}", self.count))
+        }
+
+        fn part2(&self) -> Result<String, String> {
+            Err(String::from("No part 2"))
+        }
+    }
+
+    fn run_lines(input: &str) -> String {
+        let mut counter = Counter { count: 0 };
+        let mut output: Vec<u8> = Vec::new();
+        run(&mut counter, input.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_run_1() {
+        assert_eq!(
+            run_lines("add 3\n\nadd 4\ncount\n"),
+            "> 3\n> > 7\n> 7\n> \n"
+        );
+    }
+
+    #[test]
+    fn test_run_2() {
+        assert_eq!(
+            run_lines("add x\nsub 1\nquit\ncount\n"),
+            "> Error: Failed to parse <n> from 'x'\n> Error: Unknown command 'sub', try help\n> \n"
+        );
+    }
+
+    #[test]
+    fn test_run_3() {
+        assert_eq!(
+            run_lines("add 2\npart1\npart2\n"),
+            "> 2\n> 2\n> Error: No part 2\n> \n"
+        );
+        assert!(run_lines("help\n").starts_with(">   part1"));
+    }
+
+    #[test]
+    fn test_arg_pair_1() {
+        assert_eq!(arg_pair::<isize>(&["5,3"], 0, "pos"), Ok((5, 3)));
+        assert_eq!(arg_pair::<isize>(&["-1,2"], 0, "pos"), Ok((-1, 2)));
+        // What run makes of -1, 2
+        assert!(arg_pair::<isize>(&["-1,", "2"], 0, "pos").is_err());
+        assert!(arg_pair::<isize>(&["5"], 0, "pos").is_err());
+        assert!(arg_pair::<isize>(&[], 0, "pos").is_err());
+    }
+
+    #[test]
+    fn test_arg_or_1() {
+        assert_eq!(arg_or::<usize>(&[], 0, "n", 10), Ok(10));
+        assert_eq!(arg_or::<usize>(&["2"], 0, "n", 10), Ok(2));
+    }
+}
@@ -7,7 +7,7 @@ extern crate regex;
 use std::env;
 use std::fs;
 
-fn solve_part1(input: &str) -> usize {
+fn parse_things(input: &str) -> Vec<(usize, &str)> {
     lazy_static! {
         static ref RE: regex::Regex = regex::Regex::new(r"(\d*) ([a-z]*)").unwrap();
     }
@@ -21,11 +21,52 @@ fn solve_part1(input: &str) -> usize {
             let thing = captures.get(2).unwrap().as_str();
             (count, thing)
         })
-        .fold(0, |acc, (count, _)| acc + count)
+        .collect()
+}
+
+fn count_things(things: &[(usize, &str)]) -> usize {
+    things.iter().fold(0, |acc, (count, _)| acc + count)
+}
+
+fn solve_part1(input: &str) -> usize {
+    count_things(&parse_things(input))
 }
 
 fn solve_part2(input: &str) -> usize {
-    input.len()
+    parse_things(input).len()
+}
+
+struct Repl<'a> {
+    things: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Repl<'a> {
+    fn new(input: &'a str) -> Repl<'a> {
+        Repl {
+            things: parse_things(input),
+        }
+    }
+}
+
+impl<'a> aoc_repl::Introspect for Repl<'a> {
+    fn commands(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![("print", "", "Print the parsed things")]
+    }
+
+    fn execute(&mut self, command: &str, _args: &[&str]) -> Result<String, String> {
+        match command {
+            "print" => Ok(format!("{:?}", self.things)),
+            _ => Err(format!("Unhandled command {}", command)),
+        }
+    }
+
+    fn part1(&self) -> Result<String, String> {
+        Ok(count_things(&self.things).to_string())
+    }
+
+    fn part2(&self) -> Result<String, String> {
+        Ok(self.things.len().to_string())
+    }
 }
 
 fn main() {
@@ -33,16 +74,24 @@ fn main() {
     const PACKAGE_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
     let filename = args
         .get(1)
-        .expect(format!("Usage: {} input-filename", PACKAGE_NAME.unwrap()).as_str());
+        .expect(format!("Usage: {} input-filename [--repl]", PACKAGE_NAME.unwrap()).as_str());
 
     println!("Reading input from {}", filename);
     let input = fs::read_to_string(filename).expect("Failed to read file");
 
-    let answer1 = solve_part1(&input);
+    if args.get(2).map(|a| a.as_str()) == Some("--repl") {
+        let mut repl = Repl::new(&input);
+        aoc_repl::run_stdio(&mut repl);
+        return;
+    }
+
+    let (answer1, stats1) = aoc_profile::measure(|| solve_part1(&input));
     println!("Answer 1: {}", answer1);
+    println!("Stats 1: {}", stats1);
 
-    let answer2 = solve_part2(&input);
+    let (answer2, stats2) = aoc_profile::measure(|| solve_part2(&input));
     println!("Answer 2: {}", answer2);
+    println!("Stats 2: {}", stats2);
 }
 
 #[cfg(test)]